@@ -55,24 +55,92 @@ defmodule NostrElixir do
   def filter_new_nif(_), do: :erlang.nif_error(:nif_not_loaded)
   def filter_to_json_nif(_), do: :erlang.nif_error(:nif_not_loaded)
   def filter_from_json_nif(_), do: :erlang.nif_error(:nif_not_loaded)
-  def nip06_generate_mnemonic_nif(_), do: :erlang.nif_error(:nif_not_loaded)
-  def nip06_mnemonic_to_seed_nif(_, _), do: :erlang.nif_error(:nif_not_loaded)
+  def nip06_generate_mnemonic_nif(_, _), do: :erlang.nif_error(:nif_not_loaded)
+  def nip06_mnemonic_to_seed_nif(_, _, _), do: :erlang.nif_error(:nif_not_loaded)
   def nip06_derive_key_nif(_, _), do: :erlang.nif_error(:nif_not_loaded)
-  def nip06_validate_mnemonic_nif(_), do: :erlang.nif_error(:nif_not_loaded)
+  def nip06_validate_mnemonic_nif(_, _), do: :erlang.nif_error(:nif_not_loaded)
   def nip44_encrypt_nif(_, _, _), do: :erlang.nif_error(:nif_not_loaded)
   def nip44_decrypt_nif(_, _, _), do: :erlang.nif_error(:nif_not_loaded)
   def nip57_private_zap_request_nif(_, _, _, _, _, _, _, _), do: :erlang.nif_error(:nif_not_loaded)
   def nip57_anonymous_zap_request_nif(_, _, _, _, _, _, _), do: :erlang.nif_error(:nif_not_loaded)
   def nip57_decrypt_sent_private_zap_message_nif(_, _, _), do: :erlang.nif_error(:nif_not_loaded)
   def nip57_decrypt_received_private_zap_message_nif(_, _), do: :erlang.nif_error(:nif_not_loaded)
-  def nip17_encrypt_dm_nif(_, _, _), do: :erlang.nif_error(:nif_not_loaded)
-  def nip17_decrypt_dm_nif(_, _, _), do: :erlang.nif_error(:nif_not_loaded)
   def nip65_create_relay_list_event_nif(_relays, _pubkey), do: :erlang.nif_error(:nif_not_loaded)
   def nip65_extract_relay_list_nif(_event_json), do: :erlang.nif_error(:nif_not_loaded)
   def nip02_create_contact_list_event_nif(_contacts, _pubkey), do: :erlang.nif_error(:nif_not_loaded)
   def nip02_extract_contacts_nif(_event_json), do: :erlang.nif_error(:nif_not_loaded)
   def nip10_create_text_note_nif(_keys_json, _content), do: :erlang.nif_error(:nif_not_loaded)
   def nip10_create_text_note_reply_nif(_keys_json, _content, _reply_to_json, _root_json, _relay_url), do: :erlang.nif_error(:nif_not_loaded)
+  def nip25_create_reaction_nif(_secret_key, _target_json, _content, _emoji_url, _relay_url), do: :erlang.nif_error(:nif_not_loaded)
+  def nip25_create_website_reaction_nif(_secret_key, _url, _content, _emoji_url), do: :erlang.nif_error(:nif_not_loaded)
+  def nip25_parse_reaction_nif(_event_json), do: :erlang.nif_error(:nif_not_loaded)
+  def nip25_aggregate_reactions_nif(_events_json), do: :erlang.nif_error(:nif_not_loaded)
+  def nip22_create_comment_nif(_secret_key, _content, _parent_json, _relay_url), do: :erlang.nif_error(:nif_not_loaded)
+  def nip22_create_external_comment_nif(_secret_key, _content, _identifier, _kind, _hint), do: :erlang.nif_error(:nif_not_loaded)
+  def nip22_parse_comment_nif(_event_json), do: :erlang.nif_error(:nif_not_loaded)
+  def nip09_create_deletion_event_nif(_secret_key, _event_ids, _coordinates, _kinds, _reason), do: :erlang.nif_error(:nif_not_loaded)
+  def nip09_apply_deletion_nif(_deletion_json, _events_json), do: :erlang.nif_error(:nif_not_loaded)
+  def nip23_create_long_form_nif(_secret_key, _content, _opts_json, _draft), do: :erlang.nif_error(:nif_not_loaded)
+  def nip23_parse_long_form_nif(_event_json, _relays), do: :erlang.nif_error(:nif_not_loaded)
+  def metadata_create_event_nif(_secret_key, _metadata_json), do: :erlang.nif_error(:nif_not_loaded)
+  def metadata_parse_nif(_event_json), do: :erlang.nif_error(:nif_not_loaded)
+  def metadata_merge_nif(_existing_json, _update_json), do: :erlang.nif_error(:nif_not_loaded)
+  def nip05_parse_nif(_identifier), do: :erlang.nif_error(:nif_not_loaded)
+  def nip05_profile_from_json_nif(_identifier, _nostr_json), do: :erlang.nif_error(:nif_not_loaded)
+  def nip05_verify_nif(_identifier, _public_key, _nostr_json), do: :erlang.nif_error(:nif_not_loaded)
+  def nip05_build_document_nif(_entries), do: :erlang.nif_error(:nif_not_loaded)
+  def nip17_send_private_msg_nif(_secret_key, _receivers, _message, _subject, _reply_to), do: :erlang.nif_error(:nif_not_loaded)
+  def nip17_unwrap_private_msg_nif(_secret_key, _gift_wrap_json), do: :erlang.nif_error(:nif_not_loaded)
+  def nip59_seal_nif(_rumor_json, _secret_key, _receiver, _tweak), do: :erlang.nif_error(:nif_not_loaded)
+  def nip59_gift_wrap_nif(_seal_json, _receiver, _extra_tags, _expiration, _tweak), do: :erlang.nif_error(:nif_not_loaded)
+  def nip59_unwrap_nif(_gift_wrap_json, _secret_key), do: :erlang.nif_error(:nif_not_loaded)
+  def nip44_conversation_key_nif(_secret_key, _public_key), do: :erlang.nif_error(:nif_not_loaded)
+  def nip44_conversation_key_from_hex_nif(_conversation_key), do: :erlang.nif_error(:nif_not_loaded)
+  def nip44_conversation_key_to_hex_nif(_conversation_key), do: :erlang.nif_error(:nif_not_loaded)
+  def nip44_encrypt_with_key_nif(_conversation_key, _content), do: :erlang.nif_error(:nif_not_loaded)
+  def nip44_decrypt_with_key_nif(_conversation_key, _payload), do: :erlang.nif_error(:nif_not_loaded)
+  def nip44_decrypt_auto_nif(_secret_key, _public_key, _payload), do: :erlang.nif_error(:nif_not_loaded)
+  def nip04_encrypt_nif(_secret_key, _public_key, _plaintext), do: :erlang.nif_error(:nif_not_loaded)
+  def nip04_decrypt_nif(_secret_key, _public_key, _ciphertext), do: :erlang.nif_error(:nif_not_loaded)
+  def nip04_create_dm_event_nif(_secret_key, _receiver, _message, _reply_to), do: :erlang.nif_error(:nif_not_loaded)
+  def nip04_decrypt_dm_event_nif(_secret_key, _event_json), do: :erlang.nif_error(:nif_not_loaded)
+  def nip49_encrypt_nif(_secret_key, _password, _log_n, _key_security), do: :erlang.nif_error(:nif_not_loaded)
+  def nip49_decrypt_nif(_ncryptsec, _password), do: :erlang.nif_error(:nif_not_loaded)
+  def nip06_derive_account_nif(_mnemonic_phrase, _passphrase, _account, _language), do: :erlang.nif_error(:nif_not_loaded)
+  def nip06_detect_language_nif(_mnemonic_phrase), do: :erlang.nif_error(:nif_not_loaded)
+  def nip06_suggest_words_nif(_prefix, _language), do: :erlang.nif_error(:nif_not_loaded)
+  def nip06_check_mnemonic_nif(_mnemonic_phrase, _language), do: :erlang.nif_error(:nif_not_loaded)
+  def nip06_valid_final_words_nif(_mnemonic_phrase, _language), do: :erlang.nif_error(:nif_not_loaded)
+  def nip06_mnemonic_from_entropy_nif(_entropy_hex, _language), do: :erlang.nif_error(:nif_not_loaded)
+  def nip06_mnemonic_from_dice_nif(_rolls, _word_count, _language), do: :erlang.nif_error(:nif_not_loaded)
+  def nip06_mnemonic_to_entropy_nif(_mnemonic_phrase, _language), do: :erlang.nif_error(:nif_not_loaded)
+  def nip06_keychain_new_nif(_mnemonic_phrase, _passphrase, _language), do: :erlang.nif_error(:nif_not_loaded)
+  def nip06_keychain_account_nif(_keychain, _account), do: :erlang.nif_error(:nif_not_loaded)
+  def nip06_keychain_list_nif(_keychain, _start, _count), do: :erlang.nif_error(:nif_not_loaded)
+  def nip06_keychain_find_account_nif(_keychain, _public_key, _max_accounts), do: :erlang.nif_error(:nif_not_loaded)
+  def nip57_validate_zap_receipt_nif(_receipt_json, _nostr_pubkey), do: :erlang.nif_error(:nif_not_loaded)
+  def nip57_validate_zap_request_nif(_request_json, _amount_msat), do: :erlang.nif_error(:nif_not_loaded)
+  def nip57_zap_receipt_nif(_request_json, _bolt11, _preimage, _paid_at, _secret_key), do: :erlang.nif_error(:nif_not_loaded)
+  def bolt11_decode_nif(_invoice), do: :erlang.nif_error(:nif_not_loaded)
+  def lnurl_encode_nif(_url), do: :erlang.nif_error(:nif_not_loaded)
+  def lnurl_decode_nif(_lnurl), do: :erlang.nif_error(:nif_not_loaded)
+  def lud16_to_lnurl_nif(_address), do: :erlang.nif_error(:nif_not_loaded)
+  def lnurl_parse_pay_response_nif(_body), do: :erlang.nif_error(:nif_not_loaded)
+  def nip57_zap_split_tags_nif(_splits), do: :erlang.nif_error(:nif_not_loaded)
+  def nip57_parse_zap_splits_nif(_event_json), do: :erlang.nif_error(:nif_not_loaded)
+  def nip57_split_amount_nif(_event_json, _amount_msat), do: :erlang.nif_error(:nif_not_loaded)
+  def nip57_split_zap_requests_nif(_event_json, _amount_msat, _relays, _message, _secret_key), do: :erlang.nif_error(:nif_not_loaded)
+  def nip57_public_zap_request_nif(_public_key, _relays, _message, _amount, _lnurl, _event_id, _event_coordinate, _secret_key_hex), do: :erlang.nif_error(:nif_not_loaded)
+  def nip57_parse_zap_request_nif(_request_json), do: :erlang.nif_error(:nif_not_loaded)
+  def nip47_parse_uri_nif(_uri), do: :erlang.nif_error(:nif_not_loaded)
+  def nip47_parse_info_nif(_uri, _info_json), do: :erlang.nif_error(:nif_not_loaded)
+  def nip47_request_nif(_uri, _method, _params_json, _encryption, _expiration), do: :erlang.nif_error(:nif_not_loaded)
+  def nip47_parse_response_nif(_uri, _event_json), do: :erlang.nif_error(:nif_not_loaded)
+  def nip47_parse_notification_nif(_uri, _event_json), do: :erlang.nif_error(:nif_not_loaded)
+  def nip47_info_event_nif(_secret_key, _methods, _encryption, _notifications), do: :erlang.nif_error(:nif_not_loaded)
+  def nip47_parse_request_nif(_secret_key, _event_json, _authorized), do: :erlang.nif_error(:nif_not_loaded)
+  def nip47_response_nif(_secret_key, _request_json, _response_json), do: :erlang.nif_error(:nif_not_loaded)
+  def nip47_notification_nif(_secret_key, _client_pubkey, _notification_json, _encryption), do: :erlang.nif_error(:nif_not_loaded)
 
 
   # Parse text tokens (convenience wrapper)
@@ -0,0 +1,90 @@
+defmodule NostrElixir.Bolt11 do
+  @moduledoc """
+  BOLT11: offline decoding of Lightning invoices.
+
+  Zap receipts (NIP-57) and Nostr Wallet Connect (NIP-47) both carry BOLT11
+  invoices. `decode/1` reads one without a Lightning node and checks its
+  signature: against the `n` tagged payee key when present, otherwise by
+  recovering the payee key from the signature.
+
+  See: https://github.com/lightning/bolts/blob/master/11-payment-encoding.md
+
+  ## Examples
+
+      iex> invoice = NostrElixir.Bolt11.decode("lnbc10u1p3unwfusp5...")
+      iex> invoice.amount_msat
+      1000000
+      iex> invoice.network
+      :bitcoin
+  """
+
+  defstruct [
+    :network,
+    :amount_msat,
+    :timestamp,
+    :expiry,
+    :expires_at,
+    :payment_hash,
+    :payment_secret,
+    :description,
+    :description_hash,
+    :min_final_cltv_expiry,
+    :payee
+  ]
+
+  @type t :: %__MODULE__{
+          network: :bitcoin | :testnet | :signet | :regtest,
+          amount_msat: non_neg_integer() | nil,
+          timestamp: non_neg_integer(),
+          expiry: non_neg_integer(),
+          expires_at: non_neg_integer(),
+          payment_hash: String.t(),
+          payment_secret: String.t() | nil,
+          description: String.t() | nil,
+          description_hash: String.t() | nil,
+          min_final_cltv_expiry: non_neg_integer(),
+          payee: String.t()
+        }
+
+  @doc """
+  Decode a BOLT11 invoice (with or without a `lightning:` prefix).
+
+  `amount_msat` is `nil` for invoices without an amount. Raises if the invoice is
+  malformed or its signature does not match the payee node key.
+  """
+  def decode(invoice) do
+    case NostrElixir.bolt11_decode_nif(invoice) do
+      {:error, reason} ->
+        raise ArgumentError, "BOLT11 decode failed: #{reason}"
+
+      result ->
+        decoded = Jason.decode!(result)
+
+        %__MODULE__{
+          network: network(decoded["network"]),
+          amount_msat: decoded["amount_msat"],
+          timestamp: decoded["timestamp"],
+          expiry: decoded["expiry"],
+          expires_at: decoded["expires_at"],
+          payment_hash: decoded["payment_hash"],
+          payment_secret: decoded["payment_secret"],
+          description: decoded["description"],
+          description_hash: decoded["description_hash"],
+          min_final_cltv_expiry: decoded["min_final_cltv_expiry"],
+          payee: decoded["payee"]
+        }
+    end
+  end
+
+  @doc """
+  Whether the invoice has expired at `now` (unix seconds, defaults to the current time).
+  """
+  def expired?(%__MODULE__{expires_at: expires_at}, now \\ System.os_time(:second)) do
+    now >= expires_at
+  end
+
+  defp network("bitcoin"), do: :bitcoin
+  defp network("testnet"), do: :testnet
+  defp network("signet"), do: :signet
+  defp network("regtest"), do: :regtest
+end
@@ -70,6 +70,15 @@ defmodule NostrElixir.Keys do
     end
   end
 
+  @doc """
+  Secret key of a keys map (see `generate_keypair/0`), a keys JSON string or a raw hex
+  or bech32 secret key. Used by the event builders that accept any of these.
+  """
+  @spec secret_key(keys_map | String.t()) :: String.t()
+  def secret_key(%{secret_key: secret_key}), do: secret_key
+  def secret_key("{" <> _ = keys_json), do: Jason.decode!(keys_json)["secret_key"]
+  def secret_key(secret_key) when is_binary(secret_key), do: secret_key
+
   @doc "Generate a new keypair and return it in a convenient map format."
   @spec generate_keypair() :: keys_map
   def generate_keypair do
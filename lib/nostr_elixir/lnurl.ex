@@ -0,0 +1,99 @@
+defmodule NostrElixir.Lnurl do
+  @moduledoc """
+  LNURL helpers for zaps: bech32 `lnurl1...` strings (LUD-01), lightning addresses
+  (LUD-16) and LNURL-pay responses (LUD-06).
+
+  Fetching is left to the caller; these functions only encode, decode and parse.
+
+  See: https://github.com/lnurl/luds
+
+  ## Examples
+
+      iex> lnurl = NostrElixir.Lnurl.encode("https://service.com/api?q=1")
+      iex> NostrElixir.Lnurl.decode(lnurl)
+      "https://service.com/api?q=1"
+
+      iex> NostrElixir.Lnurl.from_lightning_address("alice@example.com").url
+      "https://example.com/.well-known/lnurlp/alice"
+  """
+
+  defmodule PayResponse do
+    @moduledoc """
+    Struct for the fields of a LUD-06 `payRequest` response used by zaps.
+
+    * `:min_sendable` / `:max_sendable` - bounds in millisatoshis
+    * `:nostr_pubkey` - key that signs zap receipts, only set when `:allows_nostr` is true
+    """
+    defstruct [
+      :callback,
+      :min_sendable,
+      :max_sendable,
+      :metadata,
+      :comment_allowed,
+      :allows_nostr,
+      :nostr_pubkey
+    ]
+  end
+
+  @doc """
+  Bech32-encode a URL as a lowercase `lnurl1...` string.
+  """
+  def encode(url) do
+    case NostrElixir.lnurl_encode_nif(url) do
+      {:error, reason} -> raise ArgumentError, "LNURL encode failed: #{reason}"
+      result -> result
+    end
+  end
+
+  @doc """
+  Decode an `lnurl1...` string (any case, optional `lightning:` prefix) to its URL.
+  """
+  def decode(lnurl) do
+    case NostrElixir.lnurl_decode_nif(lnurl) do
+      {:error, reason} -> raise ArgumentError, "LNURL decode failed: #{reason}"
+      result -> result
+    end
+  end
+
+  @doc """
+  Convert a `lud16` lightning address (`name@domain`) to its LNURL-pay URL and lnurl.
+
+  Returns `%{url: url, lnurl: lnurl}`. Onion domains use `http`.
+  """
+  def from_lightning_address(address) do
+    case NostrElixir.lud16_to_lnurl_nif(address) do
+      {:error, reason} ->
+        raise ArgumentError, "LNURL from_lightning_address failed: #{reason}"
+
+      result ->
+        decoded = Jason.decode!(result)
+        %{url: decoded["url"], lnurl: decoded["lnurl"]}
+    end
+  end
+
+  @doc """
+  Parse a fetched LNURL-pay response body into a `%PayResponse{}`.
+
+  Raises on `{"status": "ERROR"}` responses, on non-`payRequest` bodies and when
+  `allowsNostr` is set without a valid `nostrPubkey`.
+  """
+  def parse_pay_response(body) do
+    case NostrElixir.lnurl_parse_pay_response_nif(body) do
+      {:error, reason} ->
+        raise ArgumentError, "LNURL parse_pay_response failed: #{reason}"
+
+      result ->
+        decoded = Jason.decode!(result)
+
+        %PayResponse{
+          callback: decoded["callback"],
+          min_sendable: decoded["min_sendable"],
+          max_sendable: decoded["max_sendable"],
+          metadata: decoded["metadata"],
+          comment_allowed: decoded["comment_allowed"],
+          allows_nostr: decoded["allows_nostr"],
+          nostr_pubkey: decoded["nostr_pubkey"]
+        }
+    end
+  end
+end
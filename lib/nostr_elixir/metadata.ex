@@ -0,0 +1,108 @@
+defmodule NostrElixir.Metadata do
+  @moduledoc """
+  Typed profile metadata (kind 0) per NIP-01 and NIP-24.
+
+  Profiles are built and parsed with the nostr crate's `Metadata` type. URL fields
+  (`picture`, `banner`, `website`) are validated, `bot` must be a boolean and `birthday`
+  a map with `year`, `month` and/or `day`. Any other field is kept in `:custom`.
+
+  ## Examples
+
+      iex> keys = NostrElixir.Keys.generate_keypair()
+      iex> event_json = NostrElixir.Metadata.create_event(keys, %{name: "alice", picture: "https://example.com/alice.png"})
+      iex> NostrElixir.Event.verify(event_json)
+      true
+      iex> NostrElixir.Metadata.parse(event_json).name
+      "alice"
+  """
+
+  @fields [:name, :display_name, :about, :picture, :banner, :website, :nip05, :lud06, :lud16, :bot, :birthday]
+
+  defstruct @fields ++ [custom: %{}]
+
+  @type t :: %__MODULE__{
+          name: String.t() | nil,
+          display_name: String.t() | nil,
+          about: String.t() | nil,
+          picture: String.t() | nil,
+          banner: String.t() | nil,
+          website: String.t() | nil,
+          nip05: String.t() | nil,
+          lud06: String.t() | nil,
+          lud16: String.t() | nil,
+          bot: boolean() | nil,
+          birthday: map() | nil,
+          custom: map()
+        }
+
+  @doc """
+  Create and sign a profile metadata event (kind 0).
+
+  `profile` is a `%NostrElixir.Metadata{}` struct or a map with atom or string keys.
+  """
+  def create_event(keys, profile) do
+    case NostrElixir.metadata_create_event_nif(NostrElixir.Keys.secret_key(keys), profile |> to_content_map() |> Jason.encode!()) do
+      {:error, reason} -> raise ArgumentError, "Metadata create_event failed: #{reason}"
+      result -> result
+    end
+  end
+
+  @doc """
+  Parse a profile metadata event (kind 0) into a `%NostrElixir.Metadata{}` struct.
+  """
+  def parse(event_json) when is_binary(event_json) do
+    case NostrElixir.metadata_parse_nif(event_json) do
+      {:error, reason} ->
+        raise ArgumentError, "Metadata parse failed: #{reason}"
+
+      result ->
+        map = Jason.decode!(result)
+        fields = Enum.map(@fields, fn field -> {field, map[Atom.to_string(field)]} end)
+        struct(__MODULE__, [{:custom, map["custom"] || %{}} | fields])
+    end
+  end
+
+  @doc """
+  Merge a partial update into existing metadata content (the JSON content of a kind 0 event).
+
+  Fields not present in `changes` are kept, including unknown custom fields. Setting a field
+  to `nil` removes it. Returns the merged content as a JSON string.
+
+  ## Examples
+      iex> NostrElixir.Metadata.merge(~s({"name":"alice","pronouns":"she/her"}), %{about: "Hi!"}) |> Jason.decode!()
+      %{"name" => "alice", "pronouns" => "she/her", "about" => "Hi!"}
+  """
+  def merge(existing_content_json, changes) when is_binary(existing_content_json) do
+    changes_json = changes |> Map.new(fn {key, value} -> {to_string(key), value} end) |> Jason.encode!()
+
+    case NostrElixir.metadata_merge_nif(existing_content_json, changes_json) do
+      {:error, reason} -> raise ArgumentError, "Metadata merge failed: #{reason}"
+      result -> result
+    end
+  end
+
+  @doc """
+  Merge a partial update into an existing profile event and sign the result as a new kind 0 event.
+  """
+  def update_event(keys, existing_event_json, changes) when is_binary(existing_event_json) do
+    content = Jason.decode!(existing_event_json)["content"]
+    merged = content |> merge(changes) |> Jason.decode!()
+    create_event(keys, merged)
+  end
+
+  defp to_content_map(%__MODULE__{} = metadata) do
+    known =
+      @fields
+      |> Enum.map(fn field -> {Atom.to_string(field), Map.get(metadata, field)} end)
+      |> Enum.reject(fn {_field, value} -> is_nil(value) end)
+      |> Map.new()
+
+    Map.merge(metadata.custom || %{}, known)
+  end
+
+  defp to_content_map(profile) when is_map(profile) do
+    profile
+    |> Enum.reject(fn {_field, value} -> is_nil(value) end)
+    |> Map.new(fn {field, value} -> {to_string(field), value} end)
+  end
+end
@@ -5,6 +5,10 @@ defmodule NostrElixir.Mnemonic do
   This module provides functions to generate, validate, and use BIP-39 mnemonics.
   It is the recommended entry point for mnemonic-related operations in Elixir.
 
+  Every BIP-39 wordlist is supported: `:english` (the default), `:chinese_simplified`,
+  `:chinese_traditional`, `:czech`, `:french`, `:italian`, `:japanese`, `:korean`,
+  `:portuguese` and `:spanish`.
+
   ## Examples
 
       iex> mnemonic = NostrElixir.Mnemonic.generate(12)
@@ -23,38 +27,288 @@ defmodule NostrElixir.Mnemonic do
       iex> NostrElixir.Mnemonic.to_seed(mnemonic, "passphrase") |> byte_size()
       64
 
+      iex> NostrElixir.Mnemonic.generate(12, language: :japanese).language
+      :japanese
+
   """
 
-  defstruct [:phrase]
+  @languages [
+    :english,
+    :chinese_simplified,
+    :chinese_traditional,
+    :czech,
+    :french,
+    :italian,
+    :japanese,
+    :korean,
+    :portuguese,
+    :spanish
+  ]
+
+  defstruct [:phrase, language: :english]
 
-  @type t :: %__MODULE__{phrase: String.t()}
+  @type t :: %__MODULE__{phrase: String.t(), language: atom()}
+
+  @doc """
+  List the supported wordlist languages.
+  """
+  def languages, do: @languages
 
   @doc """
   Generate a new mnemonic with the given word count (12, 15, 18, 21, or 24).
+
+  ## Options
+
+  - `:language` - wordlist to use (default `:english`)
+  """
+  def generate(word_count, opts \\ []) when word_count in [12, 15, 18, 21, 24] do
+    language = Keyword.get(opts, :language, :english)
+
+    case NostrElixir.nip06_generate_mnemonic_nif(word_count, language_name(language)) do
+      {:error, reason} ->
+        raise ArgumentError, "Mnemonic generate failed: #{reason}"
+
+      result ->
+        result = Jason.decode!(result)
+        %__MODULE__{phrase: result["phrase"], language: language}
+    end
+  end
+
+  @doc """
+  Build a mnemonic from caller-supplied entropy given as a hex string (16 to 32 bytes,
+  a multiple of 4). The same entropy always yields the same phrase.
+
+  ## Options
+
+  - `:language` - wordlist to use (default `:english`)
+
+  ## Examples
+      iex> NostrElixir.Mnemonic.from_entropy("00000000000000000000000000000000").phrase
+      "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
   """
-  def generate(word_count) when word_count in [12, 15, 18, 21, 24] do
-    result = NostrElixir.nip06_generate_mnemonic_nif(word_count) |> Jason.decode!()
-    %__MODULE__{phrase: result["phrase"]}
+  def from_entropy(entropy_hex, opts \\ []) when is_binary(entropy_hex) do
+    language = Keyword.get(opts, :language, :english)
+
+    case NostrElixir.nip06_mnemonic_from_entropy_nif(entropy_hex, language_name(language)) do
+      {:error, reason} ->
+        raise ArgumentError, "Mnemonic from_entropy failed: #{reason}"
+
+      result ->
+        result = Jason.decode!(result)
+        %__MODULE__{phrase: result["phrase"], language: language}
+    end
+  end
+
+  @doc """
+  Build a mnemonic from physical dice rolls, for air-gapped key generation.
+
+  `rolls` is a string of digits 1-6 (whitespace is ignored). The rolls are hashed with
+  SHA-256 to produce the entropy, so at least 50 rolls are needed for 12 words and 100
+  for 24 words.
+
+  ## Options
+
+  - `:language` - wordlist to use (default `:english`)
+  """
+  def from_dice(rolls, word_count, opts \\ []) when word_count in [12, 15, 18, 21, 24] do
+    language = Keyword.get(opts, :language, :english)
+
+    case NostrElixir.nip06_mnemonic_from_dice_nif(rolls, word_count, language_name(language)) do
+      {:error, reason} ->
+        raise ArgumentError, "Mnemonic from_dice failed: #{reason}"
+
+      result ->
+        result = Jason.decode!(result)
+        %__MODULE__{phrase: result["phrase"], language: language}
+    end
+  end
+
+  @doc """
+  Export the entropy of a mnemonic as a hex string.
+
+  Phrases given as strings are parsed with the English wordlist unless the
+  `:language` option is given.
+  """
+  def to_entropy(mnemonic_or_phrase, opts \\ [])
+  def to_entropy(%__MODULE__{phrase: phrase, language: language}, _opts), do: to_entropy(phrase, language: language)
+
+  def to_entropy(phrase, opts) when is_binary(phrase) do
+    language = Keyword.get(opts, :language, :english)
+
+    case NostrElixir.nip06_mnemonic_to_entropy_nif(phrase, language_name(language)) do
+      {:error, reason} -> raise ArgumentError, "Mnemonic to_entropy failed: #{reason}"
+      result -> result
+    end
   end
 
   @doc """
   Create a mnemonic struct from a phrase string.
+
+  ## Options
+
+  - `:language` - wordlist of the phrase (default `:english`)
   """
-  def from_phrase(phrase) when is_binary(phrase), do: %__MODULE__{phrase: phrase}
+  def from_phrase(phrase, opts \\ []) when is_binary(phrase) do
+    %__MODULE__{phrase: phrase, language: Keyword.get(opts, :language, :english)}
+  end
 
   @doc """
   Check if a mnemonic is valid.
+
+  Phrases given as strings are checked against the English wordlist unless the
+  `:language` option is given.
   """
-  def valid?(%__MODULE__{phrase: phrase}), do: NostrElixir.nip06_validate_mnemonic_nif(phrase)
-  def valid?(phrase) when is_binary(phrase), do: NostrElixir.nip06_validate_mnemonic_nif(phrase)
+  def valid?(mnemonic_or_phrase, opts \\ [])
+  def valid?(%__MODULE__{phrase: phrase, language: language}, _opts), do: valid?(phrase, language: language)
+
+  def valid?(phrase, opts) when is_binary(phrase) do
+    language = Keyword.get(opts, :language, :english)
+
+    case NostrElixir.nip06_validate_mnemonic_nif(phrase, language_name(language)) do
+      {:error, reason} -> raise ArgumentError, "Mnemonic valid? failed: #{reason}"
+      result -> result
+    end
+  end
 
   @doc """
   Convert a mnemonic to a binary seed. Optionally takes a passphrase.
+
+  Phrases given as strings are parsed with the English wordlist unless the
+  `:language` option is given.
   """
-  def to_seed(mnemonic_or_phrase, passphrase \\ "")
-  def to_seed(%__MODULE__{phrase: phrase}, passphrase), do: to_seed(phrase, passphrase)
-  def to_seed(phrase, passphrase) when is_binary(phrase) do
-    result = NostrElixir.nip06_mnemonic_to_seed_nif(phrase, passphrase) |> Jason.decode!()
-    Base.decode16!(result["seed"], case: :lower)
+  def to_seed(mnemonic_or_phrase, passphrase \\ "", opts \\ [])
+
+  def to_seed(%__MODULE__{phrase: phrase, language: language}, passphrase, _opts),
+    do: to_seed(phrase, passphrase, language: language)
+
+  def to_seed(phrase, passphrase, opts) when is_binary(phrase) do
+    language = Keyword.get(opts, :language, :english)
+
+    case NostrElixir.nip06_mnemonic_to_seed_nif(phrase, passphrase, language_name(language)) do
+      {:error, reason} ->
+        raise ArgumentError, "Mnemonic to_seed failed: #{reason}"
+
+      result ->
+        result = Jason.decode!(result)
+        Base.decode16!(result["seed"], case: :lower)
+    end
+  end
+
+  @doc """
+  Detect the wordlist language of a phrase.
+
+  Returns `%{language: language, candidates: [language]}`. A few words appear in more
+  than one wordlist (e.g. English and French); when every word of the phrase is
+  shared, `:language` is `nil` and `:candidates` lists all matching languages.
+
+  ## Examples
+      iex> NostrElixir.Mnemonic.detect_language("ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco abierto")
+      %{language: :spanish, candidates: [:spanish]}
+  """
+  def detect_language(%__MODULE__{phrase: phrase}), do: detect_language(phrase)
+
+  def detect_language(phrase) when is_binary(phrase) do
+    case NostrElixir.nip06_detect_language_nif(phrase) do
+      {:error, reason} ->
+        raise ArgumentError, "Mnemonic detect_language failed: #{reason}"
+
+      result ->
+        result = Jason.decode!(result)
+
+        %{
+          language: result["language"] && language_atom(result["language"]),
+          candidates: Enum.map(result["candidates"], &language_atom/1)
+        }
+    end
+  end
+
+  @doc """
+  Suggest wordlist words starting with `prefix`, for autocompletion.
+
+  ## Options
+
+  - `:language` - wordlist to search (default `:english`)
+
+  ## Examples
+      iex> NostrElixir.Mnemonic.suggest_words("abs")
+      ["absent", "absorb", "abstract", "absurd"]
+  """
+  def suggest_words(prefix, opts \\ []) when is_binary(prefix) do
+    language = Keyword.get(opts, :language, :english)
+
+    case NostrElixir.nip06_suggest_words_nif(prefix, language_name(language)) do
+      {:error, reason} -> raise ArgumentError, "Mnemonic suggest_words failed: #{reason}"
+      result -> result
+    end
+  end
+
+  @doc """
+  Check a phrase and report why it is invalid.
+
+  Returns a map with:
+
+  - `:valid` - whether the phrase is a valid mnemonic
+  - `:word_count` / `:word_count_valid` - number of words and whether it is 12, 15, 18, 21 or 24
+  - `:unknown_words` - 0-based positions of words that are not in the wordlist
+  - `:checksum_error` - true when every word is known but only the checksum fails
+
+  ## Examples
+      iex> NostrElixir.Mnemonic.check("abandon abandn abandon abandon abandon abandon abandon abandon abandon abandon abandon about").unknown_words
+      [1]
+  """
+  def check(mnemonic_or_phrase, opts \\ [])
+  def check(%__MODULE__{phrase: phrase, language: language}, _opts), do: check(phrase, language: language)
+
+  def check(phrase, opts) when is_binary(phrase) do
+    language = Keyword.get(opts, :language, :english)
+
+    case NostrElixir.nip06_check_mnemonic_nif(phrase, language_name(language)) do
+      {:error, reason} ->
+        raise ArgumentError, "Mnemonic check failed: #{reason}"
+
+      result ->
+        result = Jason.decode!(result)
+
+        %{
+          valid: result["valid"],
+          word_count: result["word_count"],
+          word_count_valid: result["word_count_valid"],
+          unknown_words: result["unknown_words"],
+          checksum_error: result["checksum_error"]
+        }
+    end
   end
+
+  @doc """
+  List every final word that makes the phrase valid.
+
+  Accepts the phrase without its final word (e.g. 11 words) or a complete phrase,
+  whose final word is then ignored.
+
+  ## Examples
+      iex> words = NostrElixir.Mnemonic.valid_final_words(String.duplicate("abandon ", 11))
+      iex> "about" in words
+      true
+  """
+  def valid_final_words(mnemonic_or_phrase, opts \\ [])
+
+  def valid_final_words(%__MODULE__{phrase: phrase, language: language}, _opts),
+    do: valid_final_words(phrase, language: language)
+
+  def valid_final_words(phrase, opts) when is_binary(phrase) do
+    language = Keyword.get(opts, :language, :english)
+
+    case NostrElixir.nip06_valid_final_words_nif(phrase, language_name(language)) do
+      {:error, reason} -> raise ArgumentError, "Mnemonic valid_final_words failed: #{reason}"
+      result -> result
+    end
+  end
+
+  @doc false
+  def language_name(language) when language in @languages, do: Atom.to_string(language)
+
+  def language_name(language),
+    do: raise(ArgumentError, "unsupported mnemonic language: #{inspect(language)}")
+
+  defp language_atom(name), do: Enum.find(@languages, &(Atom.to_string(&1) == name))
 end
@@ -0,0 +1,98 @@
+defmodule NostrElixir.Nip04 do
+  @moduledoc """
+  NIP-04: Encrypted Direct Message (legacy)
+
+  This module provides NIP-04 encryption (AES-256-CBC over an ECDH shared secret) and
+  helpers for building and reading kind 4 direct message events.
+
+  NIP-04 leaks metadata and is deprecated in favour of NIP-17 (`NostrElixir.Nip17`);
+  use it only to talk to clients that do not support NIP-17 yet.
+
+  See: https://github.com/nostr-protocol/nips/blob/master/04.md
+
+  ## Examples
+
+      iex> alice = NostrElixir.Keys.generate_keypair()
+      iex> bob = NostrElixir.Keys.generate_keypair()
+      iex> event_json = NostrElixir.Nip04.create_dm_event(alice, bob.public_key, "hello!")
+      iex> NostrElixir.Nip04.decrypt_dm_event(bob, event_json).plaintext
+      "hello!"
+  """
+
+  defmodule DirectMessage do
+    @moduledoc """
+    Struct representing a decrypted kind 4 direct message.
+
+    * `:peer` - the other participant from the point of view of the decrypting keys
+    """
+    defstruct [:id, :sender, :receiver, :peer, :created_at, :plaintext]
+  end
+
+  @doc """
+  Encrypt a message with NIP-04.
+
+  Returns the `ciphertext?iv=...` payload used as kind 4 content.
+  """
+  def encrypt(secret_key, public_key, plaintext) do
+    case NostrElixir.nip04_encrypt_nif(secret_key, public_key, plaintext) do
+      {:error, reason} -> raise ArgumentError, "NIP-04 encrypt failed: #{reason}"
+      result -> result
+    end
+  end
+
+  @doc """
+  Decrypt a NIP-04 `ciphertext?iv=...` payload.
+  """
+  def decrypt(secret_key, public_key, ciphertext) do
+    case NostrElixir.nip04_decrypt_nif(secret_key, public_key, ciphertext) do
+      {:error, reason} -> raise ArgumentError, "NIP-04 decrypt failed: #{reason}"
+      result -> result
+    end
+  end
+
+  @doc """
+  Build and sign a kind 4 direct message to `receiver` with encrypted content.
+
+  ## Options
+
+  - `:reply_to` - id of the event being replied to, added as an `e` tag
+
+  ## Examples
+      iex> alice = NostrElixir.Keys.generate_keypair()
+      iex> bob = NostrElixir.Keys.generate_keypair()
+      iex> event_json = NostrElixir.Nip04.create_dm_event(alice, bob.public_key, "hi")
+      iex> Jason.decode!(event_json)["kind"]
+      4
+  """
+  def create_dm_event(keys, receiver, message, opts \\ []) do
+    case NostrElixir.nip04_create_dm_event_nif(NostrElixir.Keys.secret_key(keys), receiver, message, opts[:reply_to]) do
+      {:error, reason} -> raise ArgumentError, "NIP-04 create_dm_event failed: #{reason}"
+      result -> result
+    end
+  end
+
+  @doc """
+  Decrypt a kind 4 event as either participant.
+
+  The author decrypts with the `p` tagged receiver and the receiver with the author;
+  any other keys raise.
+  """
+  def decrypt_dm_event(keys, event_json) do
+    case NostrElixir.nip04_decrypt_dm_event_nif(NostrElixir.Keys.secret_key(keys), event_json) do
+      {:error, reason} ->
+        raise ArgumentError, "NIP-04 decrypt_dm_event failed: #{reason}"
+
+      result ->
+        decoded = Jason.decode!(result)
+
+        %DirectMessage{
+          id: decoded["id"],
+          sender: decoded["sender"],
+          receiver: decoded["receiver"],
+          peer: decoded["peer"],
+          created_at: decoded["created_at"],
+          plaintext: decoded["plaintext"]
+        }
+    end
+  end
+end
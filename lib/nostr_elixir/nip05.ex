@@ -0,0 +1,90 @@
+defmodule NostrElixir.Nip05 do
+  @moduledoc """
+  NIP-05: Mapping Nostr keys to DNS-based internet identifiers
+
+  This module parses `name@domain` identifiers and works with `/.well-known/nostr.json`
+  documents offline: fetching the document is left to the caller (e.g. with your HTTP client
+  of choice), verification and relay extraction happen on the fetched body.
+
+  See: https://github.com/nostr-protocol/nips/blob/master/05.md
+
+  ## Examples
+
+      iex> NostrElixir.Nip05.parse("bob@example.com")
+      %{name: "bob", domain: "example.com", url: "https://example.com/.well-known/nostr.json?name=bob"}
+
+      iex> body = ~s({"names":{"bob":"b0635d6a9851d3aed0cd6c495b282167acf761729078d975fc341b22650b07b9"}})
+      iex> NostrElixir.Nip05.verify("bob@example.com", "b0635d6a9851d3aed0cd6c495b282167acf761729078d975fc341b22650b07b9", body)
+      true
+  """
+
+  @doc """
+  Parse a NIP-05 identifier into its name, domain and the nostr.json URL to fetch.
+
+  Identifiers are case-insensitive and returned lowercased. A bare domain is treated as `_@domain`.
+  """
+  def parse(identifier) when is_binary(identifier) do
+    case NostrElixir.nip05_parse_nif(identifier) do
+      {:error, reason} ->
+        raise ArgumentError, "NIP-05 parse failed: #{reason}"
+
+      result ->
+        map = Jason.decode!(result)
+        %{name: map["name"], domain: map["domain"], url: map["url"]}
+    end
+  end
+
+  @doc """
+  Build the `/.well-known/nostr.json?name=` URL for an identifier.
+  """
+  def well_known_url(identifier), do: parse(identifier).url
+
+  @doc """
+  Verify that a fetched nostr.json body maps the identifier's name to `public_key` (hex or npub).
+  """
+  def verify(identifier, public_key, nostr_json) when is_binary(nostr_json) do
+    case NostrElixir.nip05_verify_nif(identifier, public_key, nostr_json) do
+      {:error, reason} -> raise ArgumentError, "NIP-05 verify failed: #{reason}"
+      result -> result
+    end
+  end
+
+  @doc """
+  Extract the profile for an identifier from a fetched nostr.json body.
+
+  Returns `%{public_key: hex, relays: [url], nip46: [url]}`. Raises if the name is not in the document.
+  """
+  def profile(identifier, nostr_json) when is_binary(nostr_json) do
+    case NostrElixir.nip05_profile_from_json_nif(identifier, nostr_json) do
+      {:error, reason} ->
+        raise ArgumentError, "NIP-05 profile failed: #{reason}"
+
+      result ->
+        map = Jason.decode!(result)
+        %{public_key: map["public_key"], relays: map["relays"], nip46: map["nip46"]}
+    end
+  end
+
+  @doc """
+  Build a nostr.json document (JSON string) for a NIP-05 service.
+
+  `entries` is a list of `{name, public_key}` or `{name, public_key, relays}` tuples;
+  public keys may be hex or npub.
+
+  ## Examples
+      iex> NostrElixir.Nip05.build_document([{"bob", "b0635d6a9851d3aed0cd6c495b282167acf761729078d975fc341b22650b07b9"}])
+      ~s({"names":{"bob":"b0635d6a9851d3aed0cd6c495b282167acf761729078d975fc341b22650b07b9"}})
+  """
+  def build_document(entries) when is_list(entries) do
+    entries =
+      Enum.map(entries, fn
+        {name, public_key} -> {name, public_key, []}
+        {name, public_key, relays} -> {name, public_key, relays}
+      end)
+
+    case NostrElixir.nip05_build_document_nif(entries) do
+      {:error, reason} -> raise ArgumentError, "NIP-05 build_document failed: #{reason}"
+      result -> result
+    end
+  end
+end
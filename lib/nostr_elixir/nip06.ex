@@ -5,8 +5,18 @@ defmodule NostrElixir.Nip06 do
   This module provides functions for deriving keys from BIP-39 mnemonics and seeds as specified in NIP-06.
   For mnemonic generation, validation, and seed conversion, use `NostrElixir.Mnemonic`.
 
+  `derive_account/2` follows NIP-06 (path `m/44'/1237'/<account>'/0/0`) and returns
+  Nostr x-only public keys. `derive_key/2` is a raw BIP-32 helper that returns the
+  33-byte compressed public key, which is not usable as a Nostr pubkey.
+
+  See: https://github.com/nostr-protocol/nips/blob/master/06.md
+
   ## Examples
 
+      iex> phrase = "leader monkey parrot ring guide accident before fence cannon height naive bean"
+      iex> NostrElixir.Nip06.derive_account(phrase).public_key
+      "17162c921dc4d2518f9a101db33695df1afb56ab82f5ff3e5da6eec3ca5cd917"
+
       iex> mnemonic = NostrElixir.Mnemonic.generate(12)
       iex> seed = NostrElixir.Mnemonic.to_seed(mnemonic)
       iex> NostrElixir.Nip06.derive_key(Base.encode16(seed, case: :lower), "m/44'/1237'/0'/0/0")
@@ -14,6 +24,57 @@ defmodule NostrElixir.Nip06 do
 
   """
 
+  defmodule Account do
+    @moduledoc """
+    Struct representing the keys of a NIP-06 account.
+    """
+    defstruct [:account, :derivation_path, :secret_key, :public_key, :nsec, :npub]
+
+    @doc """
+    Build an account from the JSON returned by the account derivation NIFs.
+    """
+    def from_json(json) do
+      decoded = Jason.decode!(json)
+
+      %__MODULE__{
+        account: decoded["account"],
+        derivation_path: decoded["derivation_path"],
+        secret_key: decoded["secret_key"],
+        public_key: decoded["public_key"],
+        nsec: decoded["nsec"],
+        npub: decoded["npub"]
+      }
+    end
+  end
+
+  @doc """
+  Derive the keys of a NIP-06 account from a mnemonic phrase.
+
+  ## Options
+
+  - `:passphrase` - optional BIP-39 passphrase (default `""`)
+  - `:account` - account index (default 0)
+  - `:language` - wordlist of the phrase (default `:english`, or the mnemonic's language)
+
+  ## Examples
+      iex> phrase = "leader monkey parrot ring guide accident before fence cannon height naive bean"
+      iex> NostrElixir.Nip06.derive_account(phrase, account: 1).derivation_path
+      "m/44'/1237'/1'/0/0"
+  """
+  def derive_account(mnemonic_or_phrase, opts \\ [])
+
+  def derive_account(%NostrElixir.Mnemonic{phrase: phrase, language: language}, opts),
+    do: derive_account(phrase, Keyword.put_new(opts, :language, language))
+
+  def derive_account(phrase, opts) when is_binary(phrase) do
+    language = NostrElixir.Mnemonic.language_name(Keyword.get(opts, :language, :english))
+
+    case NostrElixir.nip06_derive_account_nif(phrase, opts[:passphrase], Keyword.get(opts, :account, 0), language) do
+      {:error, reason} -> raise ArgumentError, "NIP-06 derive_account failed: #{reason}"
+      result -> Account.from_json(result)
+    end
+  end
+
   @doc """
   Derives a key from a seed (hex string) and derivation path.
 
@@ -0,0 +1,80 @@
+defmodule NostrElixir.Nip06.Keychain do
+  @moduledoc """
+  Multi-account NIP-06 keychain.
+
+  Runs the BIP-39 seed derivation (PBKDF2) once and keeps the `m/44'/1237'` key in
+  native memory, so any number of accounts (personas) can be derived from a single
+  mnemonic cheaply.
+
+  ## Examples
+
+      iex> keychain = NostrElixir.Nip06.Keychain.new("leader monkey parrot ring guide accident before fence cannon height naive bean")
+      iex> NostrElixir.Nip06.Keychain.account(keychain, 0).public_key
+      "17162c921dc4d2518f9a101db33695df1afb56ab82f5ff3e5da6eec3ca5cd917"
+      iex> NostrElixir.Nip06.Keychain.find_account(keychain, "17162c921dc4d2518f9a101db33695df1afb56ab82f5ff3e5da6eec3ca5cd917")
+      0
+  """
+
+  alias NostrElixir.Nip06.Account
+
+  @doc """
+  Create a keychain from a mnemonic (struct or phrase).
+
+  ## Options
+
+  - `:passphrase` - optional BIP-39 passphrase (default `""`)
+  - `:language` - wordlist of the phrase (default `:english`, or the mnemonic's language)
+  """
+  def new(mnemonic_or_phrase, opts \\ [])
+
+  def new(%NostrElixir.Mnemonic{phrase: phrase, language: language}, opts),
+    do: new(phrase, Keyword.put_new(opts, :language, language))
+
+  def new(phrase, opts) when is_binary(phrase) do
+    language = NostrElixir.Mnemonic.language_name(Keyword.get(opts, :language, :english))
+
+    case NostrElixir.nip06_keychain_new_nif(phrase, opts[:passphrase], language) do
+      {:error, reason} -> raise ArgumentError, "NIP-06 keychain failed: #{reason}"
+      result -> result
+    end
+  end
+
+  @doc """
+  Derive the keys of account `index` (`m/44'/1237'/<index>'/0/0`).
+  """
+  def account(keychain, index) do
+    case NostrElixir.nip06_keychain_account_nif(keychain, index) do
+      {:error, reason} -> raise ArgumentError, "NIP-06 keychain account failed: #{reason}"
+      result -> Account.from_json(result)
+    end
+  end
+
+  @doc """
+  List the public keys of `count` accounts (at most 1000) starting at index `:start`
+  (default 0).
+
+  Returns `[%{account: index, public_key: hex, npub: npub}]`.
+  """
+  def list(keychain, count, opts \\ []) do
+    case NostrElixir.nip06_keychain_list_nif(keychain, Keyword.get(opts, :start, 0), count) do
+      {:error, reason} ->
+        raise ArgumentError, "NIP-06 keychain list failed: #{reason}"
+
+      result ->
+        result
+        |> Jason.decode!()
+        |> Enum.map(&%{account: &1["account"], public_key: &1["public_key"], npub: &1["npub"]})
+    end
+  end
+
+  @doc """
+  Find the account index of `public_key` (hex or npub), searching the first
+  `:max_accounts` accounts (default 100, at most 1000). Returns `nil` when not found.
+  """
+  def find_account(keychain, public_key, opts \\ []) do
+    case NostrElixir.nip06_keychain_find_account_nif(keychain, public_key, Keyword.get(opts, :max_accounts, 100)) do
+      {:error, reason} -> raise ArgumentError, "NIP-06 keychain find_account failed: #{reason}"
+      result -> result
+    end
+  end
+end
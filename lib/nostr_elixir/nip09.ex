@@ -5,7 +5,8 @@ defmodule NostrElixir.Nip09 do
   This module provides helpers for creating event deletion events (kind 5) according to NIP-09.
 
   **Implementation note:**
-  > Tag construction is implemented in pure Elixir for full NIP-09 spec compliance. This ensures correct and predictable behavior for event deletion tags.
+  > `create_deletion_event/3` builds its `e` tags in pure Elixir. `create_deletion_request/2`
+  > and `apply_deletion/2` are implemented natively and also handle `a` (coordinate) and `k` (kind) tags.
 
   See: https://github.com/nostr-protocol/nips/blob/master/09.md
 
@@ -23,7 +24,7 @@ defmodule NostrElixir.Nip09 do
     @moduledoc """
     Struct representing a NIP-09 event deletion.
     """
-    defstruct [:event_ids, :reason]
+    defstruct [:event_ids, :reason, coordinates: [], kinds: []]
   end
 
   @doc """
@@ -67,10 +68,90 @@ defmodule NostrElixir.Nip09 do
 
     %Deletion{
       event_ids: event_ids,
+      coordinates: find_tag_values(tags, "a"),
+      kinds: tags |> find_tag_values("k") |> Enum.flat_map(&parse_kind/1),
       reason: event["content"]
     }
   end
 
+  @doc """
+  Create and sign a deletion request (kind 5) with `e`, `a` and `k` tags.
+
+  ## Options
+
+  - `:event_ids` - list of event IDs (hex) to delete
+  - `:coordinates` - list of addressable event coordinates (`"kind:pubkey:identifier"`) to delete;
+    they must belong to the signing key
+  - `:kinds` - kinds of the deleted events; the kinds of `:coordinates` are added automatically
+  - `:reason` - optional reason for deletion
+
+  ## Examples
+      iex> keys = NostrElixir.Keys.generate_keypair()
+      iex> coordinate = "30023:\#{keys.public_key}:my-article"
+      iex> event_json = NostrElixir.Nip09.create_deletion_request(keys, coordinates: [coordinate])
+      iex> NostrElixir.Nip09.extract_deletion(event_json).kinds
+      [30023]
+  """
+  def create_deletion_request(keys, opts) when is_list(opts) do
+    case NostrElixir.nip09_create_deletion_event_nif(
+      NostrElixir.Keys.secret_key(keys),
+      opts[:event_ids] || [],
+      opts[:coordinates] || [],
+      opts[:kinds] || [],
+      opts[:reason]
+    ) do
+      {:error, reason} -> raise ArgumentError, "NIP-09 create_deletion_request failed: #{reason}"
+      result -> result
+    end
+  end
+
+  @doc """
+  Create and sign a deletion request (kind 5) for the given events (JSON strings).
+
+  Event IDs and kinds are taken from the events; addressable events are also deleted by coordinate.
+  """
+  def create_deletion_for_events(keys, event_jsons, reason \\ nil) when is_list(event_jsons) do
+    events = Enum.map(event_jsons, &Jason.decode!/1)
+
+    coordinates =
+      for %{"kind" => kind} = event <- events, kind >= 30000 and kind < 40000 do
+        "#{kind}:#{event["pubkey"]}:#{find_tag_value(event["tags"] || [], "d") || ""}"
+      end
+
+    create_deletion_request(keys,
+      event_ids: Enum.map(events, & &1["id"]),
+      coordinates: coordinates,
+      kinds: events |> Enum.map(& &1["kind"]) |> Enum.uniq(),
+      reason: reason
+    )
+  end
+
+  @doc """
+  Apply a deletion request (kind 5) to a list of events (JSON strings).
+
+  Only events by the same author as the deletion request are removed: events referenced by `e` tags,
+  and addressable events referenced by `a` tags whose `created_at` is not later than the deletion
+  request. Deletion requests themselves are never removed. The deletion request's signature must be valid.
+
+  Returns `{kept_event_jsons, deleted_event_ids}`.
+
+  ## Examples
+      iex> keys = NostrElixir.Keys.generate_keypair()
+      iex> note_json = NostrElixir.Nip10.create_text_note(keys, "oops")
+      iex> deletion_json = NostrElixir.Nip09.create_deletion_for_events(keys, [note_json])
+      iex> {[], [_deleted_id]} = NostrElixir.Nip09.apply_deletion(deletion_json, [note_json])
+  """
+  def apply_deletion(deletion_json, event_jsons) when is_binary(deletion_json) and is_list(event_jsons) do
+    case NostrElixir.nip09_apply_deletion_nif(deletion_json, event_jsons) do
+      {:error, reason} ->
+        raise ArgumentError, "NIP-09 apply_deletion failed: #{reason}"
+
+      result ->
+        result = Jason.decode!(result)
+        {result["events"], result["deleted_ids"]}
+    end
+  end
+
   @doc """
   Pretty-print an event deletion event (shows event IDs and reason).
   """
@@ -105,6 +186,22 @@ defmodule NostrElixir.Nip09 do
     NostrElixir.event_sign_nif(event_json, secret_key)
   end
 
+  defp find_tag_value(tags, tag_name) do
+    case Enum.find(tags, fn tag -> List.first(tag) == tag_name end) do
+      nil -> nil
+      tag -> Enum.at(tag, 1)
+    end
+  end
+
+  defp parse_kind(kind) when is_binary(kind) do
+    case Integer.parse(kind) do
+      {kind, ""} -> [kind]
+      _ -> []
+    end
+  end
+
+  defp parse_kind(_), do: []
+
   defp find_tag_values(tags, tag_name) do
     tags
     |> Enum.filter(fn tag -> List.first(tag) == tag_name end)
@@ -1,34 +1,131 @@
 defmodule NostrElixir.Nip17 do
   @moduledoc """
-  NIP-17: Private Direct Messages (encrypted DMs, kind 4 events)
+  NIP-17: Private Direct Messages
+
+  Private messages are unsigned kind 14 "rumors" that are sealed (kind 13, NIP-44
+  encrypted and signed by the sender) and then gift-wrapped (kind 1059, encrypted
+  again and signed by a random one-time key) following NIP-59. Both the seal and the
+  gift wrap get a randomized `created_at` up to two days in the past to hide metadata.
+
+  `send_private_msg/4` produces one gift wrap per recipient plus one addressed to the
+  sender, so the sender can read their own messages back from relays.
+
+  `encrypt_dm/3` and `decrypt_dm/3` are kept for backwards compatibility; they only
+  perform NIP-04 encryption and do not produce NIP-17 events. See `NostrElixir.Nip04`
+  for kind 4 messages.
+
+  See: https://github.com/nostr-protocol/nips/blob/master/17.md
+
+  ## Examples
+
+      iex> alice = NostrElixir.Keys.generate_keypair()
+      iex> bob = NostrElixir.Keys.generate_keypair()
+      iex> %{gift_wraps: wraps} = NostrElixir.Nip17.send_private_msg(alice, [bob.public_key], "hello!")
+      iex> length(wraps)
+      2
+      iex> wrap = Enum.find(wraps, &(&1.receiver == bob.public_key))
+      iex> NostrElixir.Nip17.unwrap(bob, wrap.event).message
+      "hello!"
+  """
+
+  defmodule PrivateMessage do
+    @moduledoc """
+    Struct representing an unwrapped NIP-17 private message.
+
+    * `:participants` - the sender followed by every `p` tagged recipient (hex)
+    * `:rumor` - the decoded kind 14 rumor as a map
+    """
+    defstruct [:sender, :message, :kind, :created_at, :subject, :reply_to, participants: [], rumor: nil]
+  end
+
+  @doc """
+  Build a private message and gift-wrap it for every recipient and for the sender.
+
+  Returns `%{rumor_id: id, gift_wraps: [%{receiver: hex, event: json}]}`. Each gift
+  wrap should be published to the inbox relays of its receiver.
+
+  ## Options
+
+  - `:subject` - conversation title added as a `subject` tag
+  - `:reply_to` - id of the kind 14 message being replied to
 
   ## Examples
+      iex> alice = NostrElixir.Keys.generate_keypair()
+      iex> bob = NostrElixir.Keys.generate_keypair()
+      iex> result = NostrElixir.Nip17.send_private_msg(alice, [bob.public_key], "hi", subject: "Party")
+      iex> is_binary(result.rumor_id)
+      true
+  """
+  def send_private_msg(keys, receivers, message, opts \\ []) do
+    case NostrElixir.nip17_send_private_msg_nif(NostrElixir.Keys.secret_key(keys), receivers, message, opts[:subject], opts[:reply_to]) do
+      {:error, reason} ->
+        raise ArgumentError, "NIP-17 send_private_msg failed: #{reason}"
+
+      result ->
+        decoded = Jason.decode!(result)
+
+        %{
+          rumor_id: decoded["rumor_id"],
+          gift_wraps: Enum.map(decoded["gift_wraps"], &%{receiver: &1["receiver"], event: &1["event"]})
+        }
+    end
+  end
+
+  @doc """
+  Unwrap a gift-wrapped private message (kind 1059) addressed to `keys`.
 
-      iex> alias NostrElixir.Nip17
-      iex> secret_key = "..." # hex
-      iex> public_key = "..." # hex
-      iex> plaintext = "hello!"
-      iex> ciphertext = Nip17.encrypt_dm(secret_key, public_key, plaintext)
-      iex> decrypted = Nip17.decrypt_dm(secret_key, public_key, ciphertext)
-      iex> decrypted == plaintext
+  Raises if the gift wrap cannot be decrypted, if any signature is invalid, or if the
+  seal was not signed by the author of the rumor.
+
+  ## Examples
+      iex> alice = NostrElixir.Keys.generate_keypair()
+      iex> bob = NostrElixir.Keys.generate_keypair()
+      iex> %{gift_wraps: [wrap | _]} = NostrElixir.Nip17.send_private_msg(alice, [bob.public_key], "hi")
+      iex> NostrElixir.Nip17.unwrap(bob, wrap.event).sender == alice.public_key
       true
   """
+  def unwrap(keys, gift_wrap_json) do
+    case NostrElixir.nip17_unwrap_private_msg_nif(NostrElixir.Keys.secret_key(keys), gift_wrap_json) do
+      {:error, reason} ->
+        raise ArgumentError, "NIP-17 unwrap failed: #{reason}"
+
+      result ->
+        decoded = Jason.decode!(result)
+
+        %PrivateMessage{
+          sender: decoded["sender"],
+          message: decoded["message"],
+          kind: decoded["kind"],
+          created_at: decoded["created_at"],
+          subject: decoded["subject"],
+          reply_to: decoded["reply_to"],
+          participants: decoded["participants"],
+          rumor: decoded["rumor"]
+        }
+    end
+  end
 
   @doc """
-  Encrypt a private direct message (NIP-17, kind 4 event).
+  Encrypt a direct message with NIP-04.
+
+  Legacy helper: this does not build a NIP-17 message, use `send_private_msg/4`
+  (or `NostrElixir.Nip04.encrypt/3` for NIP-04).
   """
   def encrypt_dm(secret_key, public_key, plaintext) do
-    case NostrElixir.nip17_encrypt_dm_nif(secret_key, public_key, plaintext) do
+    case NostrElixir.nip04_encrypt_nif(secret_key, public_key, plaintext) do
       {:error, reason} -> raise ArgumentError, "NIP-17 encrypt_dm failed: #{reason}"
       result -> result
     end
   end
 
   @doc """
-  Decrypt a private direct message (NIP-17, kind 4 event).
+  Decrypt a direct message encrypted with NIP-04.
+
+  Legacy helper: this does not unwrap NIP-17 messages, use `unwrap/2`
+  (or `NostrElixir.Nip04.decrypt/3` for NIP-04).
   """
   def decrypt_dm(secret_key, public_key, ciphertext) do
-    case NostrElixir.nip17_decrypt_dm_nif(secret_key, public_key, ciphertext) do
+    case NostrElixir.nip04_decrypt_nif(secret_key, public_key, ciphertext) do
       {:error, reason} -> raise ArgumentError, "NIP-17 decrypt_dm failed: #{reason}"
       result -> result
     end
@@ -0,0 +1,126 @@
+defmodule NostrElixir.Nip22 do
+  @moduledoc """
+  NIP-22: Comments
+
+  This module provides helpers for creating and parsing comment events (kind 1111).
+  Unlike kind 1 replies, comments can target any event kind (e.g. NIP-23 articles),
+  addressable events and external content such as URLs.
+
+  Every comment carries a root scope (`E`/`A`/`I`, `K`, `P` tags) and a parent scope
+  (`e`/`a`/`i`, `k`, `p` tags). When replying to another comment, the root scope is
+  copied from that comment.
+
+  See: https://github.com/nostr-protocol/nips/blob/master/22.md
+
+  ## Examples
+
+      iex> keys = NostrElixir.Keys.generate_keypair()
+      iex> article_json = NostrElixir.Nip23.create_long_form(keys, "# Article", %{title: "Article"})
+      iex> comment_json = NostrElixir.Nip22.create_comment(keys, "Great read!", article_json)
+      iex> NostrElixir.Event.verify(comment_json)
+      true
+      iex> NostrElixir.Nip22.parse_comment(comment_json).root.kind
+      "30023"
+  """
+
+  defmodule Scope do
+    @moduledoc """
+    Struct representing the root or parent scope of a NIP-22 comment.
+
+    * `:type` - "event", "address" or "external"
+    * `:kind` - the event kind or external content type (e.g. "web"), as a string
+    """
+    defstruct [:type, :event_id, :coordinate, :external, :kind, :pubkey, :relay_hint]
+  end
+
+  defmodule Comment do
+    @moduledoc """
+    Struct representing a parsed NIP-22 comment.
+    """
+    defstruct [:id, :pubkey, :created_at, :content, :root, :parent]
+  end
+
+  @doc """
+  Create and sign a comment (kind 1111) on an event.
+
+  If `parent_event_json` is itself a comment, the new comment is a reply in the same
+  thread and inherits its root scope; otherwise the parent event becomes the root.
+
+  ## Options
+
+  - `:relay_url` - relay hint added to the scope tags
+
+  ## Examples
+      iex> keys = NostrElixir.Keys.generate_keypair()
+      iex> article_json = NostrElixir.Nip23.create_long_form(keys, "Content", %{title: "T"})
+      iex> comment_json = NostrElixir.Nip22.create_comment(keys, "First!", article_json)
+      iex> reply_json = NostrElixir.Nip22.create_comment(keys, "Second!", comment_json)
+      iex> is_binary(reply_json)
+      true
+  """
+  def create_comment(keys, content, parent_event_json, opts \\ []) do
+    case NostrElixir.nip22_create_comment_nif(NostrElixir.Keys.secret_key(keys), content, parent_event_json, opts[:relay_url]) do
+      {:error, reason} -> raise ArgumentError, "NIP-22 create_comment failed: #{reason}"
+      result -> result
+    end
+  end
+
+  @doc """
+  Create and sign a top-level comment (kind 1111) on external content (NIP-73).
+
+  `identifier` is a URL or another NIP-73 identifier such as `"isbn:9780765382030"`.
+
+  ## Options
+
+  - `:kind` - the external content type for the `K`/`k` tags; inferred when omitted (e.g. "web" for URLs)
+  - `:hint` - URL hint for the `I`/`i` tags
+
+  ## Examples
+      iex> keys = NostrElixir.Keys.generate_keypair()
+      iex> comment_json = NostrElixir.Nip22.create_external_comment(keys, "Nice post", "https://example.com/post")
+      iex> NostrElixir.Nip22.parse_comment(comment_json).root.kind
+      "web"
+  """
+  def create_external_comment(keys, content, identifier, opts \\ []) do
+    case NostrElixir.nip22_create_external_comment_nif(NostrElixir.Keys.secret_key(keys), content, identifier, opts[:kind], opts[:hint]) do
+      {:error, reason} -> raise ArgumentError, "NIP-22 create_external_comment failed: #{reason}"
+      result -> result
+    end
+  end
+
+  @doc """
+  Parse a comment event (kind 1111) into a %Comment{} struct with root and parent %Scope{}s.
+  """
+  def parse_comment(event_json) when is_binary(event_json) do
+    case NostrElixir.nip22_parse_comment_nif(event_json) do
+      {:error, reason} ->
+        raise ArgumentError, "NIP-22 parse_comment failed: #{reason}"
+
+      result ->
+        map = Jason.decode!(result)
+
+        %Comment{
+          id: map["id"],
+          pubkey: map["pubkey"],
+          created_at: map["created_at"],
+          content: map["content"],
+          root: to_scope(map["root"]),
+          parent: to_scope(map["parent"])
+        }
+    end
+  end
+
+  defp to_scope(nil), do: nil
+
+  defp to_scope(map) do
+    %Scope{
+      type: map["type"],
+      event_id: map["event_id"],
+      coordinate: map["coordinate"],
+      external: map["external"],
+      kind: map["kind"],
+      pubkey: map["pubkey"],
+      relay_hint: map["relay_hint"]
+    }
+  end
+end
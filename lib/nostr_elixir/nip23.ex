@@ -5,7 +5,9 @@ defmodule NostrElixir.Nip23 do
   This module provides helpers for creating long-form content events (kind 30023) according to NIP-23.
 
   **Implementation note:**
-  > Tag construction is implemented in pure Elixir for full NIP-23 spec compliance. This ensures correct and predictable behavior for all required and optional tags, and makes it easy to adapt to future spec changes.
+  > `create_long_form/3` and `extract_metadata/1` build and read tags in pure Elixir. `create_article/3`,
+  > `create_draft/3` and `parse_article/2` use the nostr crate's NIP-23 builders natively, require a `d`
+  > identifier and also compute the addressable coordinate and `naddr`.
 
   See: https://github.com/nostr-protocol/nips/blob/master/23.md
 
@@ -36,7 +38,11 @@ defmodule NostrElixir.Nip23 do
       :hashtags,
       :canonical,
       :lang,
-      :content
+      :content,
+      :identifier,
+      :coordinate,
+      :naddr,
+      draft: false
     ]
   end
 
@@ -92,6 +98,71 @@ defmodule NostrElixir.Nip23 do
     }
   end
 
+  @doc """
+  Create and sign a long-form article (kind 30023) using the native NIP-23 builder.
+
+  ## Options
+
+  - `:identifier` - (required) The `d` tag identifying the article
+  - `:title` - (optional) The article title
+  - `:summary` - (optional) A brief summary of the article
+  - `:image` - (optional) URL to a cover image
+  - `:published_at` - (optional) Unix timestamp when the article was first published
+  - `:hashtags` - (optional) List of hashtags (without the # symbol); stored lowercased
+
+  ## Examples
+      iex> keys = NostrElixir.Keys.generate_keypair()
+      iex> event_json = NostrElixir.Nip23.create_article(keys, "# Hello", %{identifier: "hello", title: "Hello"})
+      iex> NostrElixir.Event.verify(event_json)
+      true
+  """
+  def create_article(keys, content, opts) do
+    create_native_long_form(keys, content, opts, false)
+  end
+
+  @doc """
+  Create and sign a long-form draft (kind 30024). Takes the same options as `create_article/3`.
+  """
+  def create_draft(keys, content, opts) do
+    create_native_long_form(keys, content, opts, true)
+  end
+
+  @doc """
+  Parse a long-form article or draft (kind 30023/30024) into a %LongForm{} struct,
+  including its `d` identifier, coordinate (`"kind:pubkey:identifier"`) and `naddr`.
+
+  `relays` are added to the `naddr` as relay hints.
+
+  ## Examples
+      iex> keys = NostrElixir.Keys.generate_keypair()
+      iex> event_json = NostrElixir.Nip23.create_article(keys, "# Hello", %{identifier: "hello"})
+      iex> article = NostrElixir.Nip23.parse_article(event_json, ["wss://relay.damus.io"])
+      iex> String.starts_with?(article.naddr, "naddr1")
+      true
+  """
+  def parse_article(event_json, relays \\ []) when is_binary(event_json) and is_list(relays) do
+    case NostrElixir.nip23_parse_long_form_nif(event_json, relays) do
+      {:error, reason} ->
+        raise ArgumentError, "NIP-23 parse_article failed: #{reason}"
+
+      result ->
+        map = Jason.decode!(result)
+
+        %LongForm{
+          title: map["title"],
+          summary: map["summary"],
+          image: map["image"],
+          published_at: map["published_at"],
+          hashtags: map["hashtags"],
+          content: map["content"],
+          identifier: map["identifier"],
+          coordinate: map["coordinate"],
+          naddr: map["naddr"],
+          draft: map["draft"]
+        }
+    end
+  end
+
   @doc """
   Pretty-print a long-form content event (shows title, summary, and content preview).
   """
@@ -138,6 +209,16 @@ defmodule NostrElixir.Nip23 do
     tags
   end
 
+  defp create_native_long_form(keys, content, opts, draft) do
+    opts_json = opts |> Map.new() |> Jason.encode!()
+    secret_key = if is_map(keys), do: keys.secret_key, else: Jason.decode!(keys)["secret_key"]
+
+    case NostrElixir.nip23_create_long_form_nif(secret_key, content, opts_json, draft) do
+      {:error, reason} -> raise ArgumentError, "NIP-23 create_long_form failed: #{reason}"
+      result -> result
+    end
+  end
+
   defp create_and_sign_event(keys, content, kind, tags) do
     tags_json = Jason.encode!(tags)
     pubkey = if is_map(keys), do: keys.public_key, else: Jason.decode!(keys)["public_key"]
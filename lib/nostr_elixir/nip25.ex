@@ -0,0 +1,146 @@
+defmodule NostrElixir.Nip25 do
+  @moduledoc """
+  NIP-25: Reactions
+
+  This module provides helpers for creating and parsing reaction events (kind 7) and
+  website reactions (kind 17), and for aggregating reaction counts per target.
+
+  See: https://github.com/nostr-protocol/nips/blob/master/25.md
+
+  ## Examples
+
+      iex> keys = NostrElixir.Keys.generate_keypair()
+      iex> note_json = NostrElixir.Nip10.create_text_note(keys, "Hello, Nostr!")
+      iex> reaction_json = NostrElixir.Nip25.create_reaction(keys, note_json, "+")
+      iex> NostrElixir.Event.verify(reaction_json)
+      true
+      iex> NostrElixir.Nip25.parse_reaction(reaction_json).reaction_type
+      "like"
+  """
+
+  defmodule Reaction do
+    @moduledoc """
+    Struct representing a parsed NIP-25 reaction.
+
+    * `:reaction_type` - "like", "dislike", "emoji" or "custom_emoji"
+    * `:target` - the reacted-to coordinate, event ID or URL (in that order of preference)
+    * `:author` - public key of the author of the reacted-to event
+    * `:target_kind` - kind of the reacted-to event, from the `k` tag
+    """
+    defstruct [
+      :id,
+      :pubkey,
+      :kind,
+      :created_at,
+      :content,
+      :reaction_type,
+      :shortcode,
+      :emoji_url,
+      :target,
+      :event_id,
+      :coordinate,
+      :url,
+      :author,
+      :target_kind
+    ]
+  end
+
+  @doc """
+  Create and sign a reaction event (kind 7) to the given event.
+
+  `content` is `"+"` (like), `"-"` (dislike), an emoji, or a custom `:shortcode:` emoji.
+
+  ## Options
+
+  - `:emoji_url` - image URL for a custom `:shortcode:` emoji (required for shortcodes)
+  - `:relay_url` - relay hint added to the `e` and `a` tags
+
+  ## Examples
+      iex> keys = NostrElixir.Keys.generate_keypair()
+      iex> note_json = NostrElixir.Nip10.create_text_note(keys, "GM")
+      iex> reaction_json = NostrElixir.Nip25.create_reaction(keys, note_json, ":soap:", emoji_url: "https://example.com/soap.png")
+      iex> is_binary(reaction_json)
+      true
+  """
+  def create_reaction(keys, target_event_json, content \\ "+", opts \\ []) do
+    case NostrElixir.nip25_create_reaction_nif(
+      NostrElixir.Keys.secret_key(keys),
+      target_event_json,
+      content,
+      opts[:emoji_url],
+      opts[:relay_url]
+    ) do
+      {:error, reason} -> raise ArgumentError, "NIP-25 create_reaction failed: #{reason}"
+      result -> result
+    end
+  end
+
+  @doc """
+  Create and sign a website reaction event (kind 17) for the given URL.
+
+  ## Options
+
+  - `:emoji_url` - image URL for a custom `:shortcode:` emoji (required for shortcodes)
+  """
+  def create_website_reaction(keys, url, content \\ "+", opts \\ []) do
+    case NostrElixir.nip25_create_website_reaction_nif(NostrElixir.Keys.secret_key(keys), url, content, opts[:emoji_url]) do
+      {:error, reason} -> raise ArgumentError, "NIP-25 create_website_reaction failed: #{reason}"
+      result -> result
+    end
+  end
+
+  @doc """
+  Parse a reaction event (kind 7 or 17) into a %Reaction{} struct.
+
+  An empty content is reported as `"+"`, as NIP-25 interprets it as a like.
+  """
+  def parse_reaction(event_json) when is_binary(event_json) do
+    case NostrElixir.nip25_parse_reaction_nif(event_json) do
+      {:error, reason} ->
+        raise ArgumentError, "NIP-25 parse_reaction failed: #{reason}"
+
+      result ->
+        map = Jason.decode!(result)
+
+        %Reaction{
+          id: map["id"],
+          pubkey: map["pubkey"],
+          kind: map["kind"],
+          created_at: map["created_at"],
+          content: map["content"],
+          reaction_type: map["reaction_type"],
+          shortcode: map["shortcode"],
+          emoji_url: map["emoji_url"],
+          target: map["target"],
+          event_id: map["event_id"],
+          coordinate: map["coordinate"],
+          url: map["url"],
+          author: map["author"],
+          target_kind: map["target_kind"]
+        }
+    end
+  end
+
+  @doc """
+  Aggregate a list of reaction event JSON strings into counts per target and reaction.
+
+  Each author is counted once per target and reaction. Events that are not reactions,
+  or whose signature does not verify, are ignored.
+
+  Returns `%{target => %{reaction => count}}`.
+
+  ## Examples
+      iex> alice = NostrElixir.Keys.generate_keypair()
+      iex> note_json = NostrElixir.Nip10.create_text_note(alice, "GM")
+      iex> reaction = NostrElixir.Nip25.create_reaction(alice, note_json, "+")
+      iex> note_id = Jason.decode!(note_json)["id"]
+      iex> NostrElixir.Nip25.aggregate([reaction, reaction])
+      %{note_id => %{"+" => 1}}
+  """
+  def aggregate(reaction_jsons) when is_list(reaction_jsons) do
+    case NostrElixir.nip25_aggregate_reactions_nif(reaction_jsons) do
+      {:error, reason} -> raise ArgumentError, "NIP-25 aggregate failed: #{reason}"
+      result -> Jason.decode!(result)
+    end
+  end
+end
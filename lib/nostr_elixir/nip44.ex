@@ -15,6 +15,12 @@ defmodule NostrElixir.Nip44 do
       iex> Nip44.decrypt(sk, pk, ciphertext)
       "hello!"
 
+  When talking to the same peer repeatedly, derive the conversation key once and reuse it:
+
+      iex> key = Nip44.conversation_key(sk, pk)
+      iex> Nip44.decrypt_with_key(key, Nip44.encrypt_with_key(key, "hello!"))
+      "hello!"
+
   """
 
   @doc """
@@ -52,4 +58,95 @@ defmodule NostrElixir.Nip44 do
       result -> result
     end
   end
+
+  @doc """
+  Derive the NIP-44 conversation key between `secret_key` and `public_key`.
+
+  Returns an opaque reference that can be passed to `encrypt_with_key/2` and
+  `decrypt_with_key/2`. The key is symmetric: both peers derive the same one.
+  """
+  def conversation_key(secret_key, public_key) do
+    case NostrElixir.nip44_conversation_key_nif(secret_key, public_key) do
+      {:error, reason} -> raise ArgumentError, "NIP-44 conversation_key failed: #{reason}"
+      result -> result
+    end
+  end
+
+  @doc """
+  Load a conversation key from its 32-byte hex representation.
+  """
+  def conversation_key_from_hex(hex) do
+    case NostrElixir.nip44_conversation_key_from_hex_nif(hex) do
+      {:error, reason} -> raise ArgumentError, "NIP-44 conversation_key_from_hex failed: #{reason}"
+      result -> result
+    end
+  end
+
+  @doc """
+  Export a conversation key as hex (e.g. to compare against the NIP-44 test vectors).
+
+  ## Examples
+      iex> sk = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364139"
+      iex> pk = "0000000000000000000000000000000000000000000000000000000000000002"
+      iex> sk |> NostrElixir.Nip44.conversation_key(pk) |> NostrElixir.Nip44.conversation_key_to_hex()
+      "8b6392dbf2ec6a2b2d5b1477fc2be84d63ef254b667cadd31bd3f444c44ae6ba"
+  """
+  def conversation_key_to_hex(conversation_key) do
+    NostrElixir.nip44_conversation_key_to_hex_nif(conversation_key)
+  end
+
+  @doc """
+  Encrypt a message (NIP-44 v2) with a conversation key from `conversation_key/2`.
+  """
+  def encrypt_with_key(conversation_key, content) do
+    case NostrElixir.nip44_encrypt_with_key_nif(conversation_key, content) do
+      {:error, reason} -> raise ArgumentError, "NIP-44 encrypt failed: #{reason}"
+      result -> result
+    end
+  end
+
+  @doc """
+  Decrypt a NIP-44 (v2) payload with a conversation key from `conversation_key/2`.
+  """
+  def decrypt_with_key(conversation_key, payload) do
+    case NostrElixir.nip44_decrypt_with_key_nif(conversation_key, payload) do
+      {:error, reason} -> raise ArgumentError, "NIP-44 decrypt failed: #{reason}"
+      result -> result
+    end
+  end
+
+  @doc """
+  Decrypt a payload that may be either legacy NIP-04 (`ciphertext?iv=...`) or NIP-44.
+
+  The scheme is detected from the payload itself. Returns a map with the plaintext and
+  the scheme that was used, so legacy messages can be flagged:
+
+      %{scheme: :nip04, version: nil, plaintext: "..."}
+      %{scheme: :nip44, version: 2, plaintext: "..."}
+
+  ## Examples
+      iex> alice = NostrElixir.Keys.generate_keypair()
+      iex> bob = NostrElixir.Keys.generate_keypair()
+      iex> payload = NostrElixir.Nip44.encrypt(alice.secret_key, bob.public_key, "hi")
+      iex> NostrElixir.Nip44.decrypt_auto(bob.secret_key, alice.public_key, payload).scheme
+      :nip44
+  """
+  def decrypt_auto(secret_key, public_key, payload) do
+    case NostrElixir.nip44_decrypt_auto_nif(secret_key, public_key, payload) do
+      {:error, reason} ->
+        raise ArgumentError, "NIP-44 decrypt_auto failed: #{reason}"
+
+      result ->
+        decoded = Jason.decode!(result)
+
+        %{
+          scheme: scheme(decoded["scheme"]),
+          version: decoded["version"],
+          plaintext: decoded["plaintext"]
+        }
+    end
+  end
+
+  defp scheme("nip04"), do: :nip04
+  defp scheme("nip44"), do: :nip44
 end
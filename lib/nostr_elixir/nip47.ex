@@ -0,0 +1,484 @@
+defmodule NostrElixir.Nip47 do
+  @moduledoc """
+  NIP-47: Nostr Wallet Connect
+
+  Client side: parse `nostr+walletconnect://` URIs, build encrypted kind 23194 requests
+  and read the wallet's kind 23195 responses and kind 23196/23197 notifications.
+
+  Wallet service side: publish the kind 13194 info event with `info_event/3`, open
+  incoming requests with `parse_request/3` and answer them with `respond/3` or
+  `respond_error/4`. Payment notifications are built with `notification_event/5`.
+
+  Requests are encrypted with NIP-44 (`:nip44_v2`) or NIP-04 (`:nip04`). Pick the scheme
+  from the wallet's kind 13194 info event with `encryption/1`; responses and
+  notifications are decrypted with whichever scheme the wallet used, and the wallet
+  answers each request with the scheme it was sent with.
+
+  See: https://github.com/nostr-protocol/nips/blob/master/47.md
+
+  ## Examples
+
+      iex> conn = NostrElixir.Nip47.parse_uri("nostr+walletconnect://b889...?relay=wss%3A%2F%2Frelay.damus.io&secret=71a8...")
+      iex> info = NostrElixir.Nip47.parse_info(conn, info_event_json)
+      iex> request_json = NostrElixir.Nip47.pay_invoice(conn, "lnbc...", encryption: NostrElixir.Nip47.encryption(info))
+      iex> # publish request_json, then for the kind 23195 reply:
+      iex> NostrElixir.Nip47.parse_response(conn, response_json).result.preimage
+      "..."
+
+      iex> # wallet service
+      iex> request = NostrElixir.Nip47.parse_request(wallet_secret_key, request_json, [conn.client_pubkey])
+      iex> request.method
+      :pay_invoice
+      iex> NostrElixir.Nip47.respond(wallet_secret_key, request_json, %{preimage: "..."})
+  """
+
+  @methods %{
+    "pay_invoice" => :pay_invoice,
+    "multi_pay_invoice" => :multi_pay_invoice,
+    "pay_keysend" => :pay_keysend,
+    "multi_pay_keysend" => :multi_pay_keysend,
+    "make_invoice" => :make_invoice,
+    "lookup_invoice" => :lookup_invoice,
+    "list_transactions" => :list_transactions,
+    "get_balance" => :get_balance,
+    "get_info" => :get_info
+  }
+
+  @error_codes %{
+    "RATE_LIMITED" => :rate_limited,
+    "NOT_IMPLEMENTED" => :not_implemented,
+    "INSUFFICIENT_BALANCE" => :insufficient_balance,
+    "PAYMENT_FAILED" => :payment_failed,
+    "NOT_FOUND" => :not_found,
+    "QUOTA_EXCEEDED" => :quota_exceeded,
+    "RESTRICTED" => :restricted,
+    "UNAUTHORIZED" => :unauthorized,
+    "UNSUPPORTED_ENCRYPTION" => :unsupported_encryption,
+    "INTERNAL" => :internal,
+    "OTHER" => :other
+  }
+
+  defmodule Connection do
+    @moduledoc """
+    Struct for a parsed `nostr+walletconnect://` URI.
+
+    * `:wallet_pubkey` - the wallet service key requests are encrypted to
+    * `:secret` - the client secret key that signs requests
+    * `:client_pubkey` - public key of `:secret`
+    """
+    defstruct [:uri, :wallet_pubkey, :relays, :secret, :client_pubkey, :lud16]
+  end
+
+  defmodule Info do
+    @moduledoc """
+    Struct for a wallet's kind 13194 info event.
+
+    `:encryption` lists the supported schemes (`:nip44_v2`, `:nip04`); wallets that do
+    not advertise any only support `:nip04`.
+    """
+    defstruct [:wallet_pubkey, methods: [], notifications: [], encryption: [:nip04]]
+  end
+
+  defmodule Transaction do
+    @moduledoc """
+    Struct for a NIP-47 transaction, as returned by `make_invoice`, `lookup_invoice`,
+    `list_transactions` and payment notifications. Amounts are in millisatoshis.
+    """
+    defstruct [
+      :type,
+      :invoice,
+      :description,
+      :description_hash,
+      :preimage,
+      :payment_hash,
+      :amount,
+      :fees_paid,
+      :created_at,
+      :expires_at,
+      :settled_at,
+      :metadata
+    ]
+  end
+
+  defmodule Response do
+    @moduledoc """
+    Struct for a decrypted kind 23195 response.
+
+    * `:request_id` - id of the request event (`e` tag)
+    * `:error` - `%{code: atom, message: string}` or `nil`
+    * `:result` - typed by `:result_type`: `%{preimage: ...}` for payments,
+      `%Transaction{}` for `make_invoice`/`lookup_invoice`, a list of them for
+      `list_transactions`, `%{balance: msat}` for `get_balance` and a map for `get_info`
+    """
+    defstruct [:request_id, :result_type, :error, :result]
+  end
+
+  defmodule Request do
+    @moduledoc """
+    Struct for a decrypted kind 23194 request, as seen by the wallet service.
+
+    * `:id` - id of the request event, referenced by the response
+    * `:client_pubkey` - public key of the connection secret that signed it
+    * `:method` - method atom, or the method name for methods this library does not know
+    * `:params` - the method params as sent, a map with string keys
+    * `:expiration` - unix timestamp from the `expiration` tag, or `nil`
+    """
+    defstruct [:id, :client_pubkey, :method, :params, :encryption, :expiration]
+  end
+
+  defmodule Notification do
+    @moduledoc """
+    Struct for a decrypted kind 23196/23197 notification.
+    """
+    defstruct [:type, :transaction]
+  end
+
+  @doc """
+  Parse a `nostr+walletconnect://` URI into a `%Connection{}`.
+  """
+  def parse_uri(uri) do
+    case NostrElixir.nip47_parse_uri_nif(uri) do
+      {:error, reason} ->
+        raise ArgumentError, "NIP-47 parse_uri failed: #{reason}"
+
+      result ->
+        decoded = Jason.decode!(result)
+
+        %Connection{
+          uri: uri,
+          wallet_pubkey: decoded["wallet_pubkey"],
+          relays: decoded["relays"],
+          secret: decoded["secret"],
+          client_pubkey: decoded["client_pubkey"],
+          lud16: decoded["lud16"]
+        }
+    end
+  end
+
+  @doc """
+  Parse the connected wallet's kind 13194 info event into an `%Info{}`.
+
+  Raises if the event is not signed by the wallet.
+  """
+  def parse_info(conn, info_json) do
+    case NostrElixir.nip47_parse_info_nif(uri(conn), info_json) do
+      {:error, reason} ->
+        raise ArgumentError, "NIP-47 parse_info failed: #{reason}"
+
+      result ->
+        decoded = Jason.decode!(result)
+
+        %Info{
+          wallet_pubkey: decoded["wallet_pubkey"],
+          methods: Enum.map(decoded["methods"], &method/1),
+          notifications: Enum.map(decoded["notifications"], &notification_type/1),
+          encryption: decoded["encryption"] |> Enum.map(&encryption_scheme/1) |> Enum.reject(&is_nil/1)
+        }
+    end
+  end
+
+  @doc """
+  The encryption to use with a wallet: `:nip44_v2` when its info event advertises it,
+  `:nip04` otherwise.
+  """
+  def encryption(%Info{encryption: encryption}) do
+    if :nip44_v2 in encryption, do: :nip44_v2, else: :nip04
+  end
+
+  @doc """
+  Build a `pay_invoice` request (returns the signed kind 23194 event JSON).
+
+  ## Options
+
+  - `:amount` - amount in millisatoshis, for invoices without one
+  - `:encryption` - `:nip44_v2` or `:nip04` (default), see `encryption/1`
+  - `:expiration` - unix timestamp after which the wallet must ignore the request
+  """
+  def pay_invoice(conn, invoice, opts \\ []) do
+    request(conn, "pay_invoice", compact(%{invoice: invoice, amount: opts[:amount]}), opts)
+  end
+
+  @doc """
+  Build a `pay_keysend` request for `amount` millisatoshis to node `pubkey`.
+
+  Accepts `:preimage` and `:tlv_records` (a list of `%{type: integer, value: hex}`)
+  besides the common `:encryption` and `:expiration` options.
+  """
+  def pay_keysend(conn, amount, pubkey, opts \\ []) do
+    params = compact(%{amount: amount, pubkey: pubkey, preimage: opts[:preimage], tlv_records: opts[:tlv_records]})
+    request(conn, "pay_keysend", params, opts)
+  end
+
+  @doc """
+  Build a `make_invoice` request for `amount` millisatoshis.
+
+  Accepts `:description`, `:description_hash` and `:expiry` (seconds) besides the
+  common `:encryption` and `:expiration` options.
+  """
+  def make_invoice(conn, amount, opts \\ []) do
+    params = %{
+      amount: amount,
+      description: opts[:description],
+      description_hash: opts[:description_hash],
+      expiry: opts[:expiry]
+    }
+
+    request(conn, "make_invoice", compact(params), opts)
+  end
+
+  @doc """
+  Build a `lookup_invoice` request by `:payment_hash` or `:invoice`.
+  """
+  def lookup_invoice(conn, opts) do
+    request(conn, "lookup_invoice", compact(%{payment_hash: opts[:payment_hash], invoice: opts[:invoice]}), opts)
+  end
+
+  @doc """
+  Build a `list_transactions` request.
+
+  Accepts `:from`, `:until`, `:limit`, `:offset`, `:unpaid` and `:type`
+  (`:incoming` or `:outgoing`) besides the common `:encryption` and `:expiration` options.
+  """
+  def list_transactions(conn, opts \\ []) do
+    params =
+      compact(%{
+        from: opts[:from],
+        until: opts[:until],
+        limit: opts[:limit],
+        offset: opts[:offset],
+        unpaid: opts[:unpaid],
+        type: opts[:type] && Atom.to_string(opts[:type])
+      })
+
+    request(conn, "list_transactions", params, opts)
+  end
+
+  @doc """
+  Build a `get_balance` request.
+  """
+  def get_balance(conn, opts \\ []) do
+    request(conn, "get_balance", %{}, opts)
+  end
+
+  @doc """
+  Decrypt and parse a kind 23195 response from the connected wallet into a `%Response{}`.
+
+  Raises if the event is not signed by the wallet. Wallet errors are returned in
+  `:error`, not raised.
+  """
+  def parse_response(conn, event_json) do
+    case NostrElixir.nip47_parse_response_nif(uri(conn), event_json) do
+      {:error, reason} ->
+        raise ArgumentError, "NIP-47 parse_response failed: #{reason}"
+
+      result ->
+        decoded = Jason.decode!(result)
+        result_type = method(decoded["result_type"])
+
+        %Response{
+          request_id: decoded["request_id"],
+          result_type: result_type,
+          error: to_error(decoded["error"]),
+          result: to_result(result_type, decoded["result"])
+        }
+    end
+  end
+
+  @doc """
+  Decrypt and parse a kind 23196 (NIP-04) or 23197 (NIP-44) notification from the
+  connected wallet into a `%Notification{}`.
+  """
+  def parse_notification(conn, event_json) do
+    case NostrElixir.nip47_parse_notification_nif(uri(conn), event_json) do
+      {:error, reason} ->
+        raise ArgumentError, "NIP-47 parse_notification failed: #{reason}"
+
+      result ->
+        decoded = Jason.decode!(result)
+
+        %Notification{
+          type: notification_type(decoded["notification_type"]),
+          transaction: to_transaction(decoded["notification"])
+        }
+    end
+  end
+
+  @doc """
+  Build the wallet's kind 13194 info event (returns the signed event JSON).
+
+  `methods` are the supported method atoms, e.g. `[:pay_invoice, :get_balance]`.
+
+  ## Options
+
+  - `:encryption` - supported schemes (default `[:nip44_v2, :nip04]`)
+  - `:notifications` - supported notification types, e.g. `[:payment_received]`
+  """
+  def info_event(wallet_secret_key, methods, opts \\ []) do
+    encryption = opts |> Keyword.get(:encryption, [:nip44_v2, :nip04]) |> Enum.map(&Atom.to_string/1)
+    notifications = opts |> Keyword.get(:notifications, []) |> Enum.map(&Atom.to_string/1)
+
+    case NostrElixir.nip47_info_event_nif(wallet_secret_key, Enum.map(methods, &Atom.to_string/1), encryption, notifications) do
+      {:error, reason} -> raise ArgumentError, "NIP-47 info_event failed: #{reason}"
+      result -> result
+    end
+  end
+
+  @doc """
+  Decrypt and check an incoming kind 23194 request into a `%Request{}`.
+
+  `authorized_client_pubkeys` are the public keys of the connection secrets this wallet
+  has handed out. Raises if the request is signed by any other key, is not `p`-tagged
+  to the wallet, has a bad signature or has expired.
+
+  Requests whose `encryption` tag names an unknown scheme, or whose payload does not
+  use the tagged scheme, raise with "Unsupported encryption"; answer them with
+  `respond_error(wallet_secret_key, request_json, :unsupported_encryption, message)`.
+  Unknown methods are returned as strings so they can be answered with
+  `:not_implemented`.
+  """
+  def parse_request(wallet_secret_key, event_json, authorized_client_pubkeys) do
+    case NostrElixir.nip47_parse_request_nif(wallet_secret_key, event_json, authorized_client_pubkeys) do
+      {:error, reason} ->
+        raise ArgumentError, "NIP-47 parse_request failed: #{reason}"
+
+      result ->
+        decoded = Jason.decode!(result)
+
+        %Request{
+          id: decoded["id"],
+          client_pubkey: decoded["client_pubkey"],
+          method: method(decoded["method"]),
+          params: decoded["params"],
+          encryption: encryption_scheme(decoded["encryption"]),
+          expiration: decoded["expiration"]
+        }
+    end
+  end
+
+  @doc """
+  Build the kind 23195 response to `request_json` carrying `result` (returns the signed
+  event JSON), e.g. `%{preimage: "..."}` for `pay_invoice` or `%{balance: 21000}` for
+  `get_balance`. The request is checked the same way as in `parse_request/3`, except for
+  authorization.
+  """
+  def respond(wallet_secret_key, request_json, result) do
+    response(wallet_secret_key, request_json, %{result: result})
+  end
+
+  @doc """
+  Build the kind 23195 error response to `request_json`.
+
+  `code` is one of `:rate_limited`, `:not_implemented`, `:insufficient_balance`,
+  `:payment_failed`, `:not_found`, `:quota_exceeded`, `:restricted`, `:unauthorized`,
+  `:unsupported_encryption`, `:internal` or `:other`. Error responses can be built for
+  any method, and for requests with an unsupported encryption (sent with NIP-04).
+  """
+  def respond_error(wallet_secret_key, request_json, code, message) do
+    response(wallet_secret_key, request_json, %{error: %{code: error_code(code), message: message}})
+  end
+
+  @doc """
+  Build a payment notification for `client_pubkey` (returns the signed event JSON).
+
+  `type` is `:payment_received` or `:payment_sent` and `transaction` a map with the
+  `%Transaction{}` fields; `:preimage` and `:settled_at` are required. NIP-04 notifications are kind 23196, NIP-44 ones kind 23197.
+
+  ## Options
+
+  - `:encryption` - `:nip44_v2` or `:nip04` (default)
+  """
+  def notification_event(wallet_secret_key, client_pubkey, type, transaction, opts \\ []) do
+    transaction = transaction |> transaction_params() |> compact()
+    notification = Jason.encode!(%{notification_type: Atom.to_string(type), notification: transaction})
+    encryption = Atom.to_string(opts[:encryption] || :nip04)
+
+    case NostrElixir.nip47_notification_nif(wallet_secret_key, client_pubkey, notification, encryption) do
+      {:error, reason} -> raise ArgumentError, "NIP-47 notification_event failed: #{reason}"
+      result -> result
+    end
+  end
+
+  defp response(wallet_secret_key, request_json, response) do
+    case NostrElixir.nip47_response_nif(wallet_secret_key, request_json, Jason.encode!(response)) do
+      {:error, reason} -> raise ArgumentError, "NIP-47 response failed: #{reason}"
+      result -> result
+    end
+  end
+
+  defp request(conn, method, params, opts) do
+    encryption = Atom.to_string(opts[:encryption] || :nip04)
+
+    case NostrElixir.nip47_request_nif(uri(conn), method, Jason.encode!(params), encryption, opts[:expiration]) do
+      {:error, reason} -> raise ArgumentError, "NIP-47 #{method} failed: #{reason}"
+      result -> result
+    end
+  end
+
+  defp uri(%Connection{uri: uri}), do: uri
+  defp uri(uri) when is_binary(uri), do: uri
+
+  defp compact(params), do: params |> Enum.reject(fn {_key, value} -> is_nil(value) end) |> Map.new()
+
+  defp transaction_params(%Transaction{} = transaction), do: Map.from_struct(transaction)
+  defp transaction_params(transaction), do: transaction
+
+  defp error_code(code) do
+    case Enum.find(@error_codes, fn {_name, atom} -> atom == code end) do
+      {name, _atom} -> name
+      nil -> raise ArgumentError, "NIP-47 unknown error code: #{inspect(code)}"
+    end
+  end
+
+  defp to_error(nil), do: nil
+  defp to_error(error), do: %{code: Map.get(@error_codes, error["code"], :other), message: error["message"]}
+
+  defp to_result(_type, nil), do: nil
+  defp to_result(type, result) when type in [:pay_invoice, :multi_pay_invoice, :pay_keysend, :multi_pay_keysend], do: %{preimage: result["preimage"]}
+  defp to_result(type, result) when type in [:make_invoice, :lookup_invoice], do: to_transaction(result)
+  defp to_result(:list_transactions, result), do: Enum.map(result["transactions"], &to_transaction/1)
+  defp to_result(:get_balance, result), do: %{balance: result["balance"]}
+
+  defp to_result(:get_info, result) do
+    %{
+      alias: result["alias"],
+      color: result["color"],
+      pubkey: result["pubkey"],
+      network: result["network"],
+      block_height: result["block_height"],
+      block_hash: result["block_hash"],
+      methods: Enum.map(result["methods"] || [], &method/1),
+      notifications: Enum.map(result["notifications"] || [], &notification_type/1)
+    }
+  end
+
+  defp to_transaction(transaction) do
+    %Transaction{
+      type: transaction_type(transaction["type"]),
+      invoice: transaction["invoice"],
+      description: transaction["description"],
+      description_hash: transaction["description_hash"],
+      preimage: transaction["preimage"],
+      payment_hash: transaction["payment_hash"],
+      amount: transaction["amount"],
+      fees_paid: transaction["fees_paid"],
+      created_at: transaction["created_at"],
+      expires_at: transaction["expires_at"],
+      settled_at: transaction["settled_at"],
+      metadata: transaction["metadata"]
+    }
+  end
+
+  defp method(name), do: Map.get(@methods, name, name)
+
+  defp transaction_type("incoming"), do: :incoming
+  defp transaction_type("outgoing"), do: :outgoing
+  defp transaction_type(_), do: nil
+
+  defp notification_type("payment_received"), do: :payment_received
+  defp notification_type("payment_sent"), do: :payment_sent
+  defp notification_type(other), do: other
+
+  defp encryption_scheme("nip44_v2"), do: :nip44_v2
+  defp encryption_scheme("nip04"), do: :nip04
+  defp encryption_scheme(_), do: nil
+end
@@ -0,0 +1,73 @@
+defmodule NostrElixir.Nip49 do
+  @moduledoc """
+  NIP-49: Private Key Encryption
+
+  This module encrypts a secret key with a password into an `ncryptsec1...` string and
+  decrypts it back. The password is stretched with scrypt (cost `2^log_n`) and the key
+  is encrypted with XChaCha20-Poly1305.
+
+  scrypt is intentionally expensive, so both functions run on a dirty CPU scheduler.
+  `log_n` is capped at 20 (1 GiB of scrypt memory); `ncryptsec` strings asking for more
+  are rejected before any key derivation.
+
+  See: https://github.com/nostr-protocol/nips/blob/master/49.md
+
+  ## Examples
+
+      iex> keys = NostrElixir.Keys.generate_keypair()
+      iex> ncryptsec = NostrElixir.Nip49.encrypt(keys, "correct horse", log_n: 16)
+      iex> String.starts_with?(ncryptsec, "ncryptsec1")
+      true
+      iex> NostrElixir.Nip49.decrypt(ncryptsec, "correct horse").secret_key == keys.secret_key
+      true
+  """
+
+  @key_security %{weak: 0, medium: 1, unknown: 2}
+
+  @doc """
+  Encrypt a secret key (hex, `nsec` or keys map) with `password`.
+
+  ## Options
+
+  - `:log_n` - scrypt cost exponent (default 16, at most 20; each increment doubles time
+    and memory)
+  - `:key_security` - `:weak` (key was handled insecurely), `:medium` (it was not) or
+    `:unknown` (default)
+  """
+  def encrypt(secret_key, password, opts \\ []) do
+    log_n = Keyword.get(opts, :log_n, 16)
+    key_security = Map.fetch!(@key_security, Keyword.get(opts, :key_security, :unknown))
+
+    case NostrElixir.nip49_encrypt_nif(NostrElixir.Keys.secret_key(secret_key), password, log_n, key_security) do
+      {:error, reason} -> raise ArgumentError, "NIP-49 encrypt failed: #{reason}"
+      result -> result
+    end
+  end
+
+  @doc """
+  Decrypt an `ncryptsec1...` string with `password`.
+
+  Returns `%{secret_key: hex, public_key: hex, log_n: integer, key_security: atom}`.
+  Raises on a wrong password or malformed input.
+  """
+  def decrypt(ncryptsec, password) do
+    case NostrElixir.nip49_decrypt_nif(ncryptsec, password) do
+      {:error, reason} ->
+        raise ArgumentError, "NIP-49 decrypt failed: #{reason}"
+
+      result ->
+        decoded = Jason.decode!(result)
+
+        %{
+          secret_key: decoded["secret_key"],
+          public_key: decoded["public_key"],
+          log_n: decoded["log_n"],
+          key_security: key_security(decoded["key_security"])
+        }
+    end
+  end
+
+  defp key_security(value) do
+    Enum.find_value(@key_security, fn {name, byte} -> if byte == value, do: name end)
+  end
+end
@@ -51,6 +51,67 @@ defmodule NostrElixir.Nip57 do
     end
   end
 
+  defmodule ZapSplit do
+    @moduledoc """
+    Struct for a `zap` split tag (`["zap", public_key, relay, weight]`).
+
+    `:relay` and `:weight` are optional.
+    """
+    defstruct [:public_key, :relay, :weight]
+
+    @type t :: %__MODULE__{
+            public_key: String.t(),
+            relay: String.t() | nil,
+            weight: non_neg_integer() | nil
+          }
+  end
+
+  defmodule ZapReceipt do
+    @moduledoc """
+    Struct representing a validated kind 9735 zap receipt.
+
+    * `:sender` - author of the embedded zap request
+    * `:recipient` - the zapped public key (`p` tag)
+    * `:amount` - invoice amount in millisatoshis
+    * `:comment` - zap request content
+    * `:anonymous` - whether the request has an `anon` tag (private or anonymous zap)
+    * `:type` - `:public`, `:private` or `:anonymous`, from the request's `anon` tag
+    """
+    defstruct [
+      :id,
+      :sender,
+      :recipient,
+      :amount,
+      :event_id,
+      :event_coordinate,
+      :comment,
+      :anonymous,
+      :type,
+      :preimage,
+      :bolt11,
+      :paid_at
+    ]
+  end
+
+  @doc """
+  Create a public zap request event signed by the sender (returns JSON string).
+  """
+  def public_zap_request(%ZapRequestData{} = data, secret_key_hex) do
+    case NostrElixir.nip57_public_zap_request_nif(
+      data.public_key,
+      data.relays,
+      data.message || "",
+      data.amount,
+      data.lnurl,
+      data.event_id,
+      data.event_coordinate,
+      secret_key_hex
+    ) do
+      {:error, reason} -> raise ArgumentError, "NIP-57 public_zap_request failed: #{reason}"
+      result -> result
+    end
+  end
+
   @doc """
   Create a private zap request event (returns JSON string).
   """
@@ -88,6 +149,33 @@ defmodule NostrElixir.Nip57 do
     end
   end
 
+  @doc """
+  Parse a kind 9734 zap request event.
+
+  Returns a map with the `ZapRequestData` fields plus `:sender` (the event author) and
+  `:type`: `:public`, `:private` (an `anon` tag carrying the encrypted sender and
+  message) or `:anonymous` (an empty `anon` tag, signed by a throwaway key). For
+  private requests `:message` is empty; use the decrypt functions to recover it.
+
+  ## Examples
+      iex> Nip57.parse_zap_request(Nip57.anonymous_zap_request(data)).type
+      :anonymous
+  """
+  def parse_zap_request(event_json) do
+    case NostrElixir.nip57_parse_zap_request_nif(event_json) do
+      {:error, reason} ->
+        raise ArgumentError, "NIP-57 parse_zap_request failed: #{reason}"
+
+      result ->
+        decoded = Jason.decode!(result)
+
+        result
+        |> to_zap_request_data()
+        |> Map.from_struct()
+        |> Map.merge(%{sender: decoded["sender"], type: zap_type(decoded["type"])})
+    end
+  end
+
   @doc """
   Decrypt a sent private zap message (returns decrypted event JSON).
   """
@@ -107,4 +195,178 @@ defmodule NostrElixir.Nip57 do
       result -> result
     end
   end
+
+  @doc """
+  Validate an incoming kind 9734 zap request on an LNURL-pay server.
+
+  Applies the NIP-57 Appendix D checks: valid signature, exactly one `p` tag, at most
+  one `e` tag, a `relays` tag, and an `amount` tag (if any) equal to the invoiced amount.
+  Returns the request as a `%ZapRequestData{}` or raises.
+
+  ## Options
+
+  - `:amount` - the amount in millisatoshis the server is about to invoice
+
+  See: https://github.com/nostr-protocol/nips/blob/master/57.md#appendix-d-lnurl-server-zap-request-validation
+  """
+  def validate_zap_request(request_json, opts \\ []) do
+    case NostrElixir.nip57_validate_zap_request_nif(request_json, opts[:amount]) do
+      {:error, reason} -> raise ArgumentError, "NIP-57 validate_zap_request failed: #{reason}"
+      result -> to_zap_request_data(result)
+    end
+  end
+
+  @doc """
+  Build and sign the kind 9735 zap receipt once `bolt11` has been paid.
+
+  `request_json` must be the exact zap request JSON whose sha256 is the invoice
+  description hash; it is embedded verbatim in the `description` tag. The receipt is
+  signed with the LNURL server's `nostrPubkey` secret key.
+
+  ## Options
+
+  - `:preimage` - payment preimage (hex), added as a `preimage` tag
+  - `:paid_at` - unix timestamp of the payment, used as `created_at` (defaults to now)
+  """
+  def zap_receipt(request_json, bolt11, secret_key_hex, opts \\ []) do
+    case NostrElixir.nip57_zap_receipt_nif(request_json, bolt11, opts[:preimage], opts[:paid_at], secret_key_hex) do
+      {:error, reason} -> raise ArgumentError, "NIP-57 zap_receipt failed: #{reason}"
+      result -> result
+    end
+  end
+
+  @doc """
+  Validate a kind 9735 zap receipt against the recipient's LNURL `nostrPubkey`.
+
+  Checks that the receipt is signed by `nostr_pubkey`, that its `description` is a
+  valid kind 9734 zap request, and that the `bolt11` invoice amount and description
+  hash match that request. Returns a `%ZapReceipt{}` or raises.
+
+  See: https://github.com/nostr-protocol/nips/blob/master/57.md#appendix-f-validating-zap-receipts
+  """
+  def validate_zap_receipt(receipt_json, nostr_pubkey) do
+    case NostrElixir.nip57_validate_zap_receipt_nif(receipt_json, nostr_pubkey) do
+      {:error, reason} ->
+        raise ArgumentError, "NIP-57 validate_zap_receipt failed: #{reason}"
+
+      result ->
+        decoded = Jason.decode!(result)
+
+        %ZapReceipt{
+          id: decoded["id"],
+          sender: decoded["sender"],
+          recipient: decoded["recipient"],
+          amount: decoded["amount"],
+          event_id: decoded["event_id"],
+          event_coordinate: decoded["event_coordinate"],
+          comment: decoded["comment"],
+          anonymous: decoded["anonymous"],
+          type: zap_type(decoded["type"]),
+          preimage: decoded["preimage"],
+          bolt11: decoded["bolt11"],
+          paid_at: decoded["paid_at"]
+        }
+    end
+  end
+
+  @doc """
+  Build `zap` split tags from a list of `%ZapSplit{}` structs (or maps with the same keys).
+
+  See: https://github.com/nostr-protocol/nips/blob/master/57.md#appendix-g-zap-tag-on-other-events
+
+  ## Examples
+      iex> Nip57.zap_split_tags([%ZapSplit{public_key: pubkey, relay: "wss://relay.damus.io", weight: 1}])
+      [["zap", pubkey, "wss://relay.damus.io", "1"]]
+  """
+  def zap_split_tags(splits) when is_list(splits) do
+    splits = Enum.map(splits, fn split -> {split.public_key, Map.get(split, :relay), Map.get(split, :weight)} end)
+
+    case NostrElixir.nip57_zap_split_tags_nif(splits) do
+      {:error, reason} -> raise ArgumentError, "NIP-57 zap_split_tags failed: #{reason}"
+      result -> result
+    end
+  end
+
+  @doc """
+  Parse the `zap` split tags of an event into `%ZapSplit{}` structs.
+  """
+  def zap_splits(event_json) do
+    case NostrElixir.nip57_parse_zap_splits_nif(event_json) do
+      {:error, reason} ->
+        raise ArgumentError, "NIP-57 zap_splits failed: #{reason}"
+
+      result ->
+        result
+        |> Jason.decode!()
+        |> Enum.map(&%ZapSplit{public_key: &1["public_key"], relay: &1["relay"], weight: &1["weight"]})
+    end
+  end
+
+  @doc """
+  Split `amount` (millisatoshis) across the event's `zap` tags.
+
+  Shares are proportional to the weights. If no tag has a weight the amount is split
+  equally; if only some do, recipients without a weight get 0. Leftover millisatoshis
+  go to the largest fractional shares, so the amounts always add up to `amount`.
+
+  Returns a list of `%{public_key, relay, amount}` in tag order.
+  """
+  def split_amount(event_json, amount) do
+    case NostrElixir.nip57_split_amount_nif(event_json, amount) do
+      {:error, reason} ->
+        raise ArgumentError, "NIP-57 split_amount failed: #{reason}"
+
+      result ->
+        result
+        |> Jason.decode!()
+        |> Enum.map(&%{public_key: &1["public_key"], relay: &1["relay"], amount: &1["amount"]})
+    end
+  end
+
+  @doc """
+  Build one zap request per `zap` split recipient of an event.
+
+  Each request targets the event (and its `a` coordinate when addressable), carries the
+  recipient's share as `amount`, and adds the split's relay hint to `:relays`.
+  Recipients whose share is 0 are skipped.
+
+  Returns a list of `%{public_key, amount, zap_request}` where `zap_request` is JSON.
+
+  ## Options
+
+  - `:relays` - relays for the zap receipts (required)
+  - `:message` - zap comment (default `""`)
+  - `:secret_key` - sign the requests as this sender; anonymous requests when omitted
+  """
+  def split_zap_requests(event_json, amount, opts) do
+    relays = Keyword.fetch!(opts, :relays)
+
+    case NostrElixir.nip57_split_zap_requests_nif(event_json, amount, relays, opts[:message] || "", opts[:secret_key]) do
+      {:error, reason} ->
+        raise ArgumentError, "NIP-57 split_zap_requests failed: #{reason}"
+
+      result ->
+        result
+        |> Jason.decode!()
+        |> Enum.map(&%{public_key: &1["public_key"], amount: &1["amount"], zap_request: &1["zap_request"]})
+    end
+  end
+
+  defp to_zap_request_data(json) do
+    decoded = Jason.decode!(json)
+
+    %ZapRequestData{
+      public_key: decoded["public_key"],
+      relays: decoded["relays"],
+      message: decoded["message"],
+      amount: decoded["amount"],
+      lnurl: decoded["lnurl"],
+      event_id: decoded["event_id"],
+      event_coordinate: decoded["event_coordinate"]
+    }
+  end
+
+  defp zap_type("public"), do: :public
+  defp zap_type("private"), do: :private
+  defp zap_type("anonymous"), do: :anonymous
 end
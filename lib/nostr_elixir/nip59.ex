@@ -0,0 +1,102 @@
+defmodule NostrElixir.Nip59 do
+  @moduledoc """
+  NIP-59: Gift Wrap
+
+  This module wraps arbitrary unsigned events ("rumors") so that only the receiver
+  can read them and relays cannot learn who talks to whom:
+
+  1. `seal/3` encrypts the rumor with NIP-44 into a kind 13 seal signed by the sender.
+  2. `gift_wrap/2` encrypts the seal into a kind 1059 event signed by a random
+     one-time key and tagged with the receiver.
+  3. `unwrap/2` reverses both layers and checks that the seal author is the rumor author.
+
+  Seals and gift wraps get a randomized `created_at` (up to two days in the past by
+  default). Use the `:tweak` option to pass a custom `min..max` range in seconds; both
+  bounds are inclusive.
+
+  See: https://github.com/nostr-protocol/nips/blob/master/59.md
+
+  ## Examples
+
+      iex> alice = NostrElixir.Keys.generate_keypair()
+      iex> bob = NostrElixir.Keys.generate_keypair()
+      iex> rumor = Jason.encode!(%{pubkey: alice.public_key, created_at: 1_700_000_000, kind: 1, tags: [], content: "secret"})
+      iex> seal = NostrElixir.Nip59.seal(rumor, alice, bob.public_key)
+      iex> gift_wrap = NostrElixir.Nip59.gift_wrap(seal, bob.public_key)
+      iex> NostrElixir.Nip59.unwrap(gift_wrap, bob).rumor["content"]
+      "secret"
+  """
+
+  defmodule Unwrapped do
+    @moduledoc """
+    Struct representing an unwrapped gift wrap.
+
+    * `:sender` - hex public key of the seal (and rumor) author
+    * `:seal` - the kind 13 seal event as JSON
+    * `:rumor` - the decoded rumor as a map
+    """
+    defstruct [:sender, :seal, :rumor]
+  end
+
+  @doc """
+  Seal a rumor (unsigned event JSON authored by `sender_keys`) for `receiver`.
+
+  Any `sig` field in the rumor is dropped and a missing `id` is computed.
+
+  ## Options
+
+  - `:tweak` - range of seconds subtracted from now for `created_at`, e.g. `0..3600`
+  """
+  def seal(rumor_json, sender_keys, receiver, opts \\ []) do
+    case NostrElixir.nip59_seal_nif(rumor_json, NostrElixir.Keys.secret_key(sender_keys), receiver, tweak(opts)) do
+      {:error, reason} -> raise ArgumentError, "NIP-59 seal failed: #{reason}"
+      result -> result
+    end
+  end
+
+  @doc """
+  Gift-wrap a seal for `receiver` using a freshly generated key.
+
+  ## Options
+
+  - `:tags` - extra tags added to the gift wrap (list of lists of strings)
+  - `:expiration` - unix timestamp added as a NIP-40 `expiration` tag
+  - `:tweak` - range of seconds subtracted from now for `created_at`, e.g. `0..3600`
+  """
+  def gift_wrap(seal_json, receiver, opts \\ []) do
+    case NostrElixir.nip59_gift_wrap_nif(seal_json, receiver, opts[:tags] || [], opts[:expiration], tweak(opts)) do
+      {:error, reason} -> raise ArgumentError, "NIP-59 gift_wrap failed: #{reason}"
+      result -> result
+    end
+  end
+
+  @doc """
+  Unwrap a gift wrap (kind 1059) addressed to `receiver_keys`.
+
+  Raises if decryption or signature verification fails, or if the seal author does
+  not match the rumor pubkey.
+  """
+  def unwrap(gift_wrap_json, receiver_keys) do
+    case NostrElixir.nip59_unwrap_nif(gift_wrap_json, NostrElixir.Keys.secret_key(receiver_keys)) do
+      {:error, reason} ->
+        raise ArgumentError, "NIP-59 unwrap failed: #{reason}"
+
+      result ->
+        decoded = Jason.decode!(result)
+
+        %Unwrapped{
+          sender: decoded["sender"],
+          seal: decoded["seal"],
+          rumor: Jason.decode!(decoded["rumor"])
+        }
+    end
+  end
+
+  defp tweak(opts) do
+    case opts[:tweak] do
+      nil -> nil
+      # The NIF takes an exclusive upper bound, Elixir ranges include it
+      min..max//_ -> {min, max + 1}
+    end
+  end
+end
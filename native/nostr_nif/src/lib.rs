@@ -1,8 +1,7 @@
-use rustler::NifResult;
+use rustler::{NifResult, ResourceArc};
 use nostr::prelude::*;
 use nostr::{Event, EventBuilder, EventId, Filter, Kind, Tag, Timestamp};
 use nostr::secp256k1::Message;
-use serde_json;
 use std::str::FromStr;
 use rustler::Encoder;
 
@@ -111,10 +110,7 @@ fn nip10_create_text_note_reply_nif(
     let secret_key = SecretKey::from_str(keys_map["secret_key"].as_str().unwrap()).unwrap();
     let keys = Keys::new(secret_key);
     let reply_to = Event::from_json(&reply_to_json).unwrap();
-    let root = match root_json {
-        Some(json) => Some(Event::from_json(&json).unwrap()),
-        None => None,
-    };
+    let root = root_json.map(|json| Event::from_json(&json).unwrap());
     let relay_url = relay_url.and_then(|url| RelayUrl::parse(&url).ok());
     let builder = EventBuilder::text_note_reply(content, &reply_to, root.as_ref(), relay_url);
     let unsigned_event = builder.build(pubkey);
@@ -148,18 +144,93 @@ rustler::init!("Elixir.NostrElixir", [
     nip44_encrypt_nif,
     nip44_decrypt_nif,
     nip57_private_zap_request_nif,
+    nip57_public_zap_request_nif,
     nip57_anonymous_zap_request_nif,
     nip57_decrypt_sent_private_zap_message_nif,
     nip57_decrypt_received_private_zap_message_nif,
-    nip17_encrypt_dm_nif,
-    nip17_decrypt_dm_nif,
     nip65_create_relay_list_event_nif,
     nip65_extract_relay_list_nif,
     nip02_create_contact_list_event_nif,
     nip02_extract_contacts_nif,
     nip10_create_text_note_nif,
     nip10_create_text_note_reply_nif,
-]);
+    nip25_create_reaction_nif,
+    nip25_create_website_reaction_nif,
+    nip25_parse_reaction_nif,
+    nip25_aggregate_reactions_nif,
+    nip22_create_comment_nif,
+    nip22_create_external_comment_nif,
+    nip22_parse_comment_nif,
+    nip09_create_deletion_event_nif,
+    nip09_apply_deletion_nif,
+    nip23_create_long_form_nif,
+    nip23_parse_long_form_nif,
+    metadata_create_event_nif,
+    metadata_parse_nif,
+    metadata_merge_nif,
+    nip05_parse_nif,
+    nip05_profile_from_json_nif,
+    nip05_verify_nif,
+    nip05_build_document_nif,
+    nip17_send_private_msg_nif,
+    nip17_unwrap_private_msg_nif,
+    nip59_seal_nif,
+    nip59_gift_wrap_nif,
+    nip59_unwrap_nif,
+    nip44_conversation_key_nif,
+    nip44_conversation_key_from_hex_nif,
+    nip44_conversation_key_to_hex_nif,
+    nip44_encrypt_with_key_nif,
+    nip44_decrypt_with_key_nif,
+    nip44_decrypt_auto_nif,
+    nip04_encrypt_nif,
+    nip04_decrypt_nif,
+    nip04_create_dm_event_nif,
+    nip04_decrypt_dm_event_nif,
+    nip49_encrypt_nif,
+    nip49_decrypt_nif,
+    nip06_derive_account_nif,
+    nip06_detect_language_nif,
+    nip06_suggest_words_nif,
+    nip06_check_mnemonic_nif,
+    nip06_valid_final_words_nif,
+    nip06_mnemonic_from_entropy_nif,
+    nip06_mnemonic_from_dice_nif,
+    nip06_mnemonic_to_entropy_nif,
+    nip06_keychain_new_nif,
+    nip06_keychain_account_nif,
+    nip06_keychain_list_nif,
+    nip06_keychain_find_account_nif,
+    nip57_validate_zap_receipt_nif,
+    nip57_validate_zap_request_nif,
+    nip57_zap_receipt_nif,
+    bolt11_decode_nif,
+    lnurl_encode_nif,
+    lnurl_decode_nif,
+    lud16_to_lnurl_nif,
+    lnurl_parse_pay_response_nif,
+    nip57_zap_split_tags_nif,
+    nip57_parse_zap_splits_nif,
+    nip57_split_amount_nif,
+    nip57_split_zap_requests_nif,
+    nip57_parse_zap_request_nif,
+    nip47_parse_uri_nif,
+    nip47_parse_info_nif,
+    nip47_request_nif,
+    nip47_parse_response_nif,
+    nip47_parse_notification_nif,
+    nip47_info_event_nif,
+    nip47_parse_request_nif,
+    nip47_response_nif,
+    nip47_notification_nif,
+], load = load);
+
+#[allow(non_local_definitions)]
+fn load(env: rustler::Env, _info: rustler::Term) -> bool {
+    rustler::resource!(ConversationKeyResource, env);
+    rustler::resource!(KeychainResource, env);
+    true
+}
 
 // Helper function to convert nostr errors to rustler errors
 fn to_rustler_error<T>(result: Result<T, impl std::fmt::Display>) -> NifResult<T> {
@@ -524,10 +595,44 @@ fn filter_from_json_nif(json_string: String) -> NifResult<String> {
 
 // NIP-06: Mnemonic/HD Wallet Support
 
+const BIP39_LANGUAGES: [(&str, bip39::Language); 10] = [
+    ("english", bip39::Language::English),
+    ("chinese_simplified", bip39::Language::SimplifiedChinese),
+    ("chinese_traditional", bip39::Language::TraditionalChinese),
+    ("czech", bip39::Language::Czech),
+    ("french", bip39::Language::French),
+    ("italian", bip39::Language::Italian),
+    ("japanese", bip39::Language::Japanese),
+    ("korean", bip39::Language::Korean),
+    ("portuguese", bip39::Language::Portuguese),
+    ("spanish", bip39::Language::Spanish),
+];
+
+// Resolves a BIP-39 wordlist name, defaulting to English.
+fn bip39_language(name: Option<&str>) -> NifResult<bip39::Language> {
+    match name {
+        None => Ok(bip39::Language::English),
+        Some(name) => BIP39_LANGUAGES
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, language)| *language)
+            .ok_or_else(|| rustler::Error::Term(Box::new(format!("Unsupported language: {}", name)))),
+    }
+}
+
+fn bip39_language_name(language: bip39::Language) -> &'static str {
+    BIP39_LANGUAGES
+        .iter()
+        .find(|(_, l)| *l == language)
+        .map(|(n, _)| *n)
+        .unwrap_or("english")
+}
+
 #[rustler::nif]
-fn nip06_generate_mnemonic_nif(word_count: u32) -> NifResult<String> {
-    use bip39::{Mnemonic, Language};
+fn nip06_generate_mnemonic_nif(word_count: u32, language: Option<String>) -> NifResult<String> {
+    use bip39::Mnemonic;
     use rand::RngCore;
+    let language = bip39_language(language.as_deref())?;
     let entropy_bytes = match word_count {
         12 => 16, // 128 bits
         15 => 20, // 160 bits
@@ -538,21 +643,23 @@ fn nip06_generate_mnemonic_nif(word_count: u32) -> NifResult<String> {
     };
     let mut entropy = vec![0u8; entropy_bytes];
     rand::thread_rng().fill_bytes(&mut entropy);
-    let mnemonic = Mnemonic::from_entropy_in(Language::English, &entropy).map_err(|e| rustler::Error::Term(Box::new(e.to_string())))?;
+    let mnemonic = Mnemonic::from_entropy_in(language, &entropy).map_err(|e| rustler::Error::Term(Box::new(e.to_string())))?;
     let phrase = mnemonic.to_string();
     let result = serde_json::json!({
         "phrase": phrase,
-        "word_count": word_count
+        "word_count": word_count,
+        "language": bip39_language_name(language)
     });
     Ok(result.to_string())
 }
 
 #[rustler::nif]
-fn nip06_mnemonic_to_seed_nif(mnemonic_phrase: String, passphrase: Option<String>) -> NifResult<String> {
-    use bip39::{Mnemonic, Language};
-    let mnemonic = to_rustler_error(Mnemonic::parse_in_normalized(Language::English, &mnemonic_phrase))?;
+fn nip06_mnemonic_to_seed_nif(mnemonic_phrase: String, passphrase: Option<String>, language: Option<String>) -> NifResult<String> {
+    use bip39::Mnemonic;
+    let language = bip39_language(language.as_deref())?;
+    let mnemonic = to_rustler_error(Mnemonic::parse_in(language, mnemonic_phrase))?;
     let passphrase = passphrase.unwrap_or_default();
-    let seed = mnemonic.to_seed_normalized(&passphrase);
+    let seed = mnemonic.to_seed(passphrase);
     let result = serde_json::json!({
         "seed": hex::encode(seed),
         "seed_length": seed.len()
@@ -580,14 +687,325 @@ fn nip06_derive_key_nif(seed_hex: String, derivation_path: String) -> NifResult<
     Ok(result.to_string())
 }
 
+// NIP-06 keys live under m/44'/1237'; each account is <account>'/0/0 below it.
+fn nip06_root_key(
+    secp: &bitcoin::secp256k1::Secp256k1<bitcoin::secp256k1::All>,
+    seed: &[u8],
+) -> NifResult<bitcoin::bip32::ExtendedPrivKey> {
+    use bitcoin::bip32::{ChildNumber, ExtendedPrivKey};
+    use bitcoin::Network;
+    let path = to_rustler_error(
+        [ChildNumber::from_hardened_idx(44), ChildNumber::from_hardened_idx(1237)]
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>(),
+    )?;
+    let master_key = to_rustler_error(ExtendedPrivKey::new_master(Network::Bitcoin, seed))?;
+    to_rustler_error(master_key.derive_priv(secp, &path))
+}
+
+fn nip06_account_keys(
+    secp: &bitcoin::secp256k1::Secp256k1<bitcoin::secp256k1::All>,
+    root: &bitcoin::bip32::ExtendedPrivKey,
+    account: u32,
+) -> NifResult<Keys> {
+    use bitcoin::bip32::ChildNumber;
+    let path = to_rustler_error(
+        [
+            ChildNumber::from_hardened_idx(account),
+            ChildNumber::from_normal_idx(0),
+            ChildNumber::from_normal_idx(0),
+        ]
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>(),
+    )?;
+    let derived_key = to_rustler_error(root.derive_priv(secp, &path))?;
+    let secret_key = to_rustler_error(SecretKey::from_slice(&derived_key.private_key.secret_bytes()))?;
+    Ok(Keys::new(secret_key))
+}
+
+fn nip06_account_json(keys: &Keys, account: u32) -> NifResult<serde_json::Value> {
+    Ok(serde_json::json!({
+        "account": account,
+        "derivation_path": format!("m/44'/1237'/{}'/0/0", account),
+        "secret_key": keys.secret_key().to_secret_hex(),
+        "public_key": keys.public_key().to_hex(),
+        "nsec": to_rustler_error(keys.secret_key().to_bech32())?,
+        "npub": to_rustler_error(keys.public_key().to_bech32())?
+    }))
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn nip06_derive_account_nif(
+    mnemonic_phrase: String,
+    passphrase: Option<String>,
+    account: u32,
+    language: Option<String>,
+) -> NifResult<String> {
+    use bip39::Mnemonic;
+    let language = bip39_language(language.as_deref())?;
+    let mnemonic = to_rustler_error(Mnemonic::parse_in(language, mnemonic_phrase))?;
+    let seed = mnemonic.to_seed(passphrase.unwrap_or_default());
+    let secp = bitcoin::secp256k1::Secp256k1::new();
+    let root = nip06_root_key(&secp, &seed)?;
+    let keys = nip06_account_keys(&secp, &root, account)?;
+    Ok(nip06_account_json(&keys, account)?.to_string())
+}
+
+// NIP-06: HD keychain
+//
+// Holds the m/44'/1237' key of a seed so accounts can be derived without running the
+// BIP-39 PBKDF2 again.
+
+pub struct KeychainResource {
+    secp: bitcoin::secp256k1::Secp256k1<bitcoin::secp256k1::All>,
+    root: bitcoin::bip32::ExtendedPrivKey,
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn nip06_keychain_new_nif(
+    mnemonic_phrase: String,
+    passphrase: Option<String>,
+    language: Option<String>,
+) -> NifResult<ResourceArc<KeychainResource>> {
+    use bip39::Mnemonic;
+    let language = bip39_language(language.as_deref())?;
+    let mnemonic = to_rustler_error(Mnemonic::parse_in(language, mnemonic_phrase))?;
+    let seed = mnemonic.to_seed(passphrase.unwrap_or_default());
+    let secp = bitcoin::secp256k1::Secp256k1::new();
+    let root = nip06_root_key(&secp, &seed)?;
+    Ok(ResourceArc::new(KeychainResource { secp, root }))
+}
+
+#[rustler::nif]
+fn nip06_keychain_account_nif(keychain: ResourceArc<KeychainResource>, account: u32) -> NifResult<String> {
+    let keys = nip06_account_keys(&keychain.secp, &keychain.root, account)?;
+    Ok(nip06_account_json(&keys, account)?.to_string())
+}
+
+// Upper bound on the accounts derived by a single list or search call.
+const NIP06_MAX_ACCOUNTS: u32 = 1000;
+
+fn nip06_check_account_count(count: u32) -> NifResult<()> {
+    if count > NIP06_MAX_ACCOUNTS {
+        return Err(rustler::Error::Term(Box::new(format!("At most {} accounts can be derived at once", NIP06_MAX_ACCOUNTS))));
+    }
+    Ok(())
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn nip06_keychain_list_nif(keychain: ResourceArc<KeychainResource>, start: u32, count: u32) -> NifResult<String> {
+    nip06_check_account_count(count)?;
+    let mut accounts = Vec::new();
+    for account in start..start.saturating_add(count) {
+        let keys = nip06_account_keys(&keychain.secp, &keychain.root, account)?;
+        accounts.push(serde_json::json!({
+            "account": account,
+            "public_key": keys.public_key().to_hex(),
+            "npub": to_rustler_error(keys.public_key().to_bech32())?
+        }));
+    }
+    Ok(serde_json::Value::Array(accounts).to_string())
+}
+
+// Searches accounts 0..max_accounts for `public_key` (hex or npub).
+#[rustler::nif(schedule = "DirtyCpu")]
+fn nip06_keychain_find_account_nif(
+    keychain: ResourceArc<KeychainResource>,
+    public_key: String,
+    max_accounts: u32,
+) -> NifResult<Option<u32>> {
+    nip06_check_account_count(max_accounts)?;
+    let public_key = to_rustler_error(PublicKey::parse(&public_key))?;
+    for account in 0..max_accounts {
+        if nip06_account_keys(&keychain.secp, &keychain.root, account)?.public_key() == public_key {
+            return Ok(Some(account));
+        }
+    }
+    Ok(None)
+}
+
 #[rustler::nif]
-fn nip06_validate_mnemonic_nif(mnemonic_phrase: String) -> NifResult<bool> {
-    use bip39::{Mnemonic, Language};
-    match Mnemonic::parse_in_normalized(Language::English, &mnemonic_phrase) {
+fn nip06_validate_mnemonic_nif(mnemonic_phrase: String, language: Option<String>) -> NifResult<bool> {
+    use bip39::Mnemonic;
+    let language = bip39_language(language.as_deref())?;
+    match Mnemonic::parse_in(language, mnemonic_phrase) {
         Ok(_) => Ok(true),
         Err(_) => Ok(false)
     }
-} 
+}
+
+fn bip39_normalize(phrase: &str) -> String {
+    let mut phrase = std::borrow::Cow::from(phrase);
+    bip39::Mnemonic::normalize_utf8_cow(&mut phrase);
+    phrase.into_owned()
+}
+
+#[rustler::nif]
+fn nip06_suggest_words_nif(prefix: String, language: Option<String>) -> NifResult<Vec<String>> {
+    let language = bip39_language(language.as_deref())?;
+    let prefix = bip39_normalize(prefix.trim());
+    if prefix.is_empty() {
+        return Ok(Vec::new());
+    }
+    Ok(language.words_by_prefix(&prefix).iter().map(|w| w.to_string()).collect())
+}
+
+#[rustler::nif]
+fn nip06_mnemonic_from_entropy_nif(entropy_hex: String, language: Option<String>) -> NifResult<String> {
+    use bip39::Mnemonic;
+    let language = bip39_language(language.as_deref())?;
+    let entropy = to_rustler_error(hex::decode(entropy_hex.trim()))?;
+    let mnemonic = to_rustler_error(Mnemonic::from_entropy_in(language, &entropy))?;
+    let result = serde_json::json!({
+        "phrase": mnemonic.to_string(),
+        "word_count": mnemonic.word_count(),
+        "language": bip39_language_name(language)
+    });
+    Ok(result.to_string())
+}
+
+// Dice rolls (digits 1-6, whitespace ignored) are hashed with SHA-256 and the first
+// 16-32 bytes used as entropy. Each roll carries ~2.585 bits, so at least 50 rolls
+// are required for 12 words and 100 for 24 words.
+#[rustler::nif]
+fn nip06_mnemonic_from_dice_nif(rolls: String, word_count: u32, language: Option<String>) -> NifResult<String> {
+    use bip39::Mnemonic;
+    use nostr::hashes::{sha256, Hash};
+    let language = bip39_language(language.as_deref())?;
+    let rolls: String = rolls.chars().filter(|c| !c.is_whitespace()).collect();
+    if let Some(c) = rolls.chars().find(|c| !('1'..='6').contains(c)) {
+        return Err(rustler::Error::Term(Box::new(format!("Invalid dice roll: {}", c))));
+    }
+    let entropy_bytes = match word_count {
+        12 => 16,
+        15 => 20,
+        18 => 24,
+        21 => 28,
+        24 => 32,
+        _ => return Err(rustler::Error::Term(Box::new("Invalid word count. Must be 12, 15, 18, 21, or 24".to_string())))
+    };
+    // ceil(bits / log2(6))
+    let min_rolls = ((entropy_bytes * 8) as f64 / 6f64.log2()).ceil() as usize;
+    if rolls.len() < min_rolls {
+        return Err(rustler::Error::Term(Box::new(format!(
+            "Not enough dice rolls: {} given, {} required for {} words",
+            rolls.len(),
+            min_rolls,
+            word_count
+        ))));
+    }
+    let hash = sha256::Hash::hash(rolls.as_bytes());
+    let mnemonic = to_rustler_error(Mnemonic::from_entropy_in(language, &hash.to_byte_array()[..entropy_bytes]))?;
+    let result = serde_json::json!({
+        "phrase": mnemonic.to_string(),
+        "word_count": word_count,
+        "language": bip39_language_name(language)
+    });
+    Ok(result.to_string())
+}
+
+#[rustler::nif]
+fn nip06_mnemonic_to_entropy_nif(mnemonic_phrase: String, language: Option<String>) -> NifResult<String> {
+    use bip39::Mnemonic;
+    let language = bip39_language(language.as_deref())?;
+    let mnemonic = to_rustler_error(Mnemonic::parse_in(language, mnemonic_phrase))?;
+    Ok(hex::encode(mnemonic.to_entropy()))
+}
+
+// Reports what is wrong with a phrase: a bad word count, the (0-based) positions of
+// words missing from the wordlist, or, when all words are known, a checksum failure.
+#[rustler::nif]
+fn nip06_check_mnemonic_nif(mnemonic_phrase: String, language: Option<String>) -> NifResult<String> {
+    use bip39::Mnemonic;
+    let language = bip39_language(language.as_deref())?;
+    let phrase = bip39_normalize(&mnemonic_phrase);
+    let words: Vec<&str> = phrase.split_whitespace().collect();
+    let word_count_valid = matches!(words.len(), 12 | 15 | 18 | 21 | 24);
+    let unknown_words: Vec<usize> = words
+        .iter()
+        .enumerate()
+        .filter(|(_, word)| language.find_word(word).is_none())
+        .map(|(i, _)| i)
+        .collect();
+    let checksum_valid = word_count_valid
+        && unknown_words.is_empty()
+        && Mnemonic::parse_in_normalized(language, &phrase).is_ok();
+    let result = serde_json::json!({
+        "valid": checksum_valid,
+        "word_count": words.len(),
+        "word_count_valid": word_count_valid,
+        "unknown_words": unknown_words,
+        "checksum_error": word_count_valid && unknown_words.is_empty() && !checksum_valid
+    });
+    Ok(result.to_string())
+}
+
+// Lists every word that completes a valid phrase. Accepts either the phrase without
+// its final word or a full phrase, in which case the final word is replaced.
+//
+// The final word holds the last 11 - CS entropy bits followed by the CS checksum bits
+// (CS = words / 3), so only 2^(11 - CS) candidates need to be hashed.
+#[rustler::nif]
+fn nip06_valid_final_words_nif(mnemonic_phrase: String, language: Option<String>) -> NifResult<Vec<String>> {
+    use nostr::hashes::{sha256, Hash};
+    let language = bip39_language(language.as_deref())?;
+    let phrase = bip39_normalize(&mnemonic_phrase);
+    let mut words: Vec<&str> = phrase.split_whitespace().collect();
+    if matches!(words.len(), 12 | 15 | 18 | 21 | 24) {
+        words.pop();
+    }
+    if !matches!(words.len() + 1, 12 | 15 | 18 | 21 | 24) {
+        return Err(rustler::Error::Term(Box::new(format!("Invalid word count: {}", words.len()))));
+    }
+    let indices = words
+        .iter()
+        .enumerate()
+        .map(|(i, word)| {
+            language
+                .find_word(word)
+                .ok_or_else(|| rustler::Error::Term(Box::new(format!("Unknown word at position {}", i))))
+        })
+        .collect::<NifResult<Vec<u16>>>()?;
+    let checksum_bits = (words.len() + 1) / 3;
+    let entropy_bits = 11 - checksum_bits;
+    let word_list = language.word_list();
+    Ok((0..1u16 << entropy_bits)
+        .map(|completion| {
+            let mut entropy = Vec::with_capacity(32);
+            let (mut acc, mut acc_bits) = (0u32, 0);
+            let groups = indices.iter().map(|&index| (index, 11));
+            for (value, bits) in groups.chain(std::iter::once((completion, entropy_bits))) {
+                acc = (acc << bits) | value as u32;
+                acc_bits += bits;
+                while acc_bits >= 8 {
+                    acc_bits -= 8;
+                    entropy.push((acc >> acc_bits) as u8);
+                }
+            }
+            let checksum = sha256::Hash::hash(&entropy).to_byte_array()[0] >> (8 - checksum_bits);
+            word_list[((completion << checksum_bits) | checksum as u16) as usize].to_string()
+        })
+        .collect())
+}
+
+// Detects the wordlist of a phrase. Some words exist in several lists (e.g. English and
+// French), so every matching language is returned and `language` is only set when
+// the match is unique.
+#[rustler::nif]
+fn nip06_detect_language_nif(mnemonic_phrase: String) -> NifResult<String> {
+    use bip39::{Error, Mnemonic};
+    let phrase = bip39_normalize(&mnemonic_phrase);
+    let candidates = match Mnemonic::language_of(&phrase) {
+        Ok(language) => vec![language],
+        Err(Error::AmbiguousLanguages(languages)) => languages.to_vec(),
+        Err(e) => return Err(rustler::Error::Term(Box::new(e.to_string()))),
+    };
+    let result = serde_json::json!({
+        "language": if candidates.len() == 1 { Some(bip39_language_name(candidates[0])) } else { None },
+        "candidates": candidates.into_iter().map(bip39_language_name).collect::<Vec<_>>()
+    });
+    Ok(result.to_string())
+}
 
 #[rustler::nif]
 fn nip44_encrypt_nif(secret_key: String, public_key: String, content: String) -> NifResult<String> {
@@ -611,19 +1029,106 @@ fn nip44_decrypt_nif(secret_key: String, public_key: String, payload: String) ->
     Ok(plaintext)
 } 
 
+// NIP-44: Cached conversation keys
+//
+// Deriving a conversation key costs an ECDH and an HKDF round. The resource keeps the
+// derived key so repeated encrypt/decrypt calls with the same peer skip that work.
+
+pub struct ConversationKeyResource {
+    key: nostr::nips::nip44::v2::ConversationKey,
+}
+
+fn nip44_encrypt_with(key: &nostr::nips::nip44::v2::ConversationKey, content: &str) -> NifResult<String> {
+    use nostr::base64::{engine::general_purpose, Engine};
+    use nostr::nips::nip44::v2;
+    let payload = to_rustler_error(v2::encrypt_to_bytes(key, content.as_bytes()))?;
+    Ok(general_purpose::STANDARD.encode(payload))
+}
+
+fn nip44_decrypt_with(key: &nostr::nips::nip44::v2::ConversationKey, payload: &str) -> NifResult<String> {
+    use nostr::base64::{engine::general_purpose, Engine};
+    use nostr::nips::nip44::{v2, Version};
+    let payload = to_rustler_error(general_purpose::STANDARD.decode(payload))?;
+    match payload.first() {
+        Some(version) if *version == Version::V2.as_u8() => {}
+        _ => return Err(rustler::Error::Term(Box::new("Unsupported NIP-44 version".to_string()))),
+    }
+    let plaintext = to_rustler_error(v2::decrypt_to_bytes(key, &payload))?;
+    to_rustler_error(String::from_utf8(plaintext))
+}
+
 #[rustler::nif]
-fn nip57_private_zap_request_nif(
+fn nip44_conversation_key_nif(secret_key: String, public_key: String) -> NifResult<ResourceArc<ConversationKeyResource>> {
+    use nostr::nips::nip44::v2::ConversationKey;
+    let sk = to_rustler_error(SecretKey::from_str(&secret_key))?;
+    let pk = to_rustler_error(PublicKey::from_str(&public_key))?;
+    let key = to_rustler_error(ConversationKey::derive(&sk, &pk))?;
+    Ok(ResourceArc::new(ConversationKeyResource { key }))
+}
+
+#[rustler::nif]
+fn nip44_conversation_key_from_hex_nif(conversation_key: String) -> NifResult<ResourceArc<ConversationKeyResource>> {
+    use nostr::nips::nip44::v2::ConversationKey;
+    let bytes = to_rustler_error(hex::decode(conversation_key))?;
+    let key = to_rustler_error(ConversationKey::from_slice(&bytes))?;
+    Ok(ResourceArc::new(ConversationKeyResource { key }))
+}
+
+#[rustler::nif]
+fn nip44_conversation_key_to_hex_nif(conversation_key: ResourceArc<ConversationKeyResource>) -> String {
+    hex::encode(conversation_key.key.as_bytes())
+}
+
+#[rustler::nif]
+fn nip44_encrypt_with_key_nif(conversation_key: ResourceArc<ConversationKeyResource>, content: String) -> NifResult<String> {
+    nip44_encrypt_with(&conversation_key.key, &content)
+}
+
+#[rustler::nif]
+fn nip44_decrypt_with_key_nif(conversation_key: ResourceArc<ConversationKeyResource>, payload: String) -> NifResult<String> {
+    nip44_decrypt_with(&conversation_key.key, &payload)
+}
+
+// Decrypts either a legacy NIP-04 payload (`<base64>?iv=<base64>`) or a NIP-44
+// payload, reporting which scheme was used.
+#[rustler::nif]
+fn nip44_decrypt_auto_nif(secret_key: String, public_key: String, payload: String) -> NifResult<String> {
+    use nostr::base64::{engine::general_purpose, Engine};
+    use nostr::nips::nip04;
+    use nostr::nips::nip44::v2::ConversationKey;
+    let sk = to_rustler_error(SecretKey::from_str(&secret_key))?;
+    let pk = to_rustler_error(PublicKey::from_str(&public_key))?;
+
+    let result = if payload.contains("?iv=") {
+        let plaintext = to_rustler_error(nip04::decrypt(&sk, &pk, &payload))?;
+        serde_json::json!({ "scheme": "nip04", "version": null, "plaintext": plaintext })
+    } else {
+        if payload.starts_with('#') {
+            return Err(rustler::Error::Term(Box::new("Unsupported NIP-44 version".to_string())));
+        }
+        let version = to_rustler_error(general_purpose::STANDARD.decode(&payload))?
+            .first()
+            .copied()
+            .ok_or_else(|| rustler::Error::Term(Box::new("Empty payload".to_string())))?;
+        let key = to_rustler_error(ConversationKey::derive(&sk, &pk))?;
+        let plaintext = nip44_decrypt_with(&key, &payload)?;
+        serde_json::json!({ "scheme": "nip44", "version": version, "plaintext": plaintext })
+    };
+    Ok(result.to_string())
+}
+
+// Builds the ZapRequestData shared by the zap request NIFs from its Elixir struct fields.
+fn nip57_zap_request_data(
     public_key: String,
     relays: Vec<String>,
     message: String,
     amount: Option<u64>,
     lnurl: Option<String>,
     event_id: Option<String>,
-    event_coordinate: Option<String>,
-    secret_key_hex: String
-) -> NifResult<String> {
-    use nostr::nips::nip57::{private_zap_request, ZapRequestData};
-    use nostr::{Keys, SecretKey, PublicKey, RelayUrl, EventId};
+    event_coordinate: Option<String>
+) -> NifResult<nostr::nips::nip57::ZapRequestData> {
+    use nostr::nips::nip57::ZapRequestData;
+    use nostr::{PublicKey, RelayUrl, EventId};
     use nostr::nips::nip01::Coordinate;
     let pk = PublicKey::from_str(&public_key).map_err(|e| rustler::Error::Term(Box::new(e.to_string())))?;
     let relays: Result<Vec<RelayUrl>, _> = relays.iter().map(|r| RelayUrl::parse(r)).collect();
@@ -633,6 +1138,37 @@ fn nip57_private_zap_request_nif(
     if let Some(l) = lnurl { data = data.lnurl(l); }
     if let Some(eid) = event_id { data = data.event_id(EventId::from_hex(&eid).map_err(|e| rustler::Error::Term(Box::new(e.to_string())))?); }
     if let Some(coord) = event_coordinate { data = data.event_coordinate(Coordinate::from_str(&coord).map_err(|e| rustler::Error::Term(Box::new(e.to_string())))?); }
+    Ok(data)
+}
+
+// Inverse of nip57_zap_request_data: the JSON shape of the Elixir ZapRequestData struct.
+fn nip57_zap_request_data_json(data: &nostr::nips::nip57::ZapRequestData) -> serde_json::Value {
+    serde_json::json!({
+        "public_key": data.public_key.to_hex(),
+        "relays": data.relays.iter().map(|r| r.to_string()).collect::<Vec<_>>(),
+        "message": data.message,
+        "amount": data.amount,
+        "lnurl": data.lnurl,
+        "event_id": data.event_id.map(|id| id.to_hex()),
+        "event_coordinate": data.event_coordinate.as_ref().map(|c| c.to_string())
+    })
+}
+
+#[rustler::nif]
+#[allow(clippy::too_many_arguments)]
+fn nip57_private_zap_request_nif(
+    public_key: String,
+    relays: Vec<String>,
+    message: String,
+    amount: Option<u64>,
+    lnurl: Option<String>,
+    event_id: Option<String>,
+    event_coordinate: Option<String>,
+    secret_key_hex: String
+) -> NifResult<String> {
+    use nostr::nips::nip57::private_zap_request;
+    use nostr::{Keys, SecretKey};
+    let data = nip57_zap_request_data(public_key, relays, message, amount, lnurl, event_id, event_coordinate)?;
     let sk = SecretKey::from_str(&secret_key_hex).map_err(|e| rustler::Error::Term(Box::new(e.to_string())))?;
     let keys = Keys::new(sk);
     let event = private_zap_request(data, &keys)
@@ -640,6 +1176,28 @@ fn nip57_private_zap_request_nif(
     Ok(serde_json::to_string(&event).unwrap())
 }
 
+#[rustler::nif]
+#[allow(clippy::too_many_arguments)]
+fn nip57_public_zap_request_nif(
+    public_key: String,
+    relays: Vec<String>,
+    message: String,
+    amount: Option<u64>,
+    lnurl: Option<String>,
+    event_id: Option<String>,
+    event_coordinate: Option<String>,
+    secret_key_hex: String
+) -> NifResult<String> {
+    use nostr::{Keys, SecretKey};
+    let data = nip57_zap_request_data(public_key, relays, message, amount, lnurl, event_id, event_coordinate)?;
+    let sk = SecretKey::from_str(&secret_key_hex).map_err(|e| rustler::Error::Term(Box::new(e.to_string())))?;
+    let keys = Keys::new(sk);
+    let event = EventBuilder::public_zap_request(data)
+        .sign_with_keys(&keys)
+        .map_err(|e| rustler::Error::Term(Box::new(e.to_string())))?;
+    Ok(serde_json::to_string(&event).unwrap())
+}
+
 #[rustler::nif]
 fn nip57_anonymous_zap_request_nif(
     public_key: String,
@@ -650,17 +1208,8 @@ fn nip57_anonymous_zap_request_nif(
     event_id: Option<String>,
     event_coordinate: Option<String>
 ) -> NifResult<String> {
-    use nostr::nips::nip57::{anonymous_zap_request, ZapRequestData};
-    use nostr::{PublicKey, RelayUrl, EventId};
-    use nostr::nips::nip01::Coordinate;
-    let pk = PublicKey::from_str(&public_key).map_err(|e| rustler::Error::Term(Box::new(e.to_string())))?;
-    let relays: Result<Vec<RelayUrl>, _> = relays.iter().map(|r| RelayUrl::parse(r)).collect();
-    let relays = relays.map_err(|e| rustler::Error::Term(Box::new(e.to_string())))?;
-    let mut data = ZapRequestData::new(pk, relays).message(message);
-    if let Some(a) = amount { data = data.amount(a); }
-    if let Some(l) = lnurl { data = data.lnurl(l); }
-    if let Some(eid) = event_id { data = data.event_id(EventId::from_hex(&eid).map_err(|e| rustler::Error::Term(Box::new(e.to_string())))?); }
-    if let Some(coord) = event_coordinate { data = data.event_coordinate(Coordinate::from_str(&coord).map_err(|e| rustler::Error::Term(Box::new(e.to_string())))?); }
+    use nostr::nips::nip57::anonymous_zap_request;
+    let data = nip57_zap_request_data(public_key, relays, message, amount, lnurl, event_id, event_coordinate)?;
     let event = anonymous_zap_request(data)
         .map_err(|e| rustler::Error::Term(Box::new(e.to_string())))?;
     Ok(serde_json::to_string(&event).unwrap())
@@ -689,26 +1238,2020 @@ fn nip57_decrypt_received_private_zap_message_nif(secret_key: String, event_json
     Ok(serde_json::to_string(&decrypted).unwrap())
 } 
 
+// NIP-25: Reactions
+
+fn nip25_emoji_tag(content: &str, emoji_url: Option<String>) -> NifResult<Option<Tag>> {
+    let shortcode = content
+        .strip_prefix(':')
+        .and_then(|s| s.strip_suffix(':'))
+        .filter(|s| !s.is_empty());
+    match (shortcode, emoji_url) {
+        (Some(shortcode), Some(url)) => {
+            let url = to_rustler_error(Url::parse(&url))?;
+            Ok(Some(to_rustler_error(Tag::parse(["emoji", shortcode, url.as_str()]))?))
+        }
+        (Some(_), None) => Err(rustler::Error::Term(Box::new("Custom emoji reaction requires an emoji URL".to_string()))),
+        (None, Some(_)) => Err(rustler::Error::Term(Box::new("Emoji URL given but content is not a :shortcode:".to_string()))),
+        (None, None) => Ok(None),
+    }
+}
+
+fn nip25_last_tag_value(event: &Event, name: &str) -> Option<String> {
+    event.tags.iter().rev().find_map(|tag| {
+        let values = tag.as_slice();
+        if values.len() >= 2 && values[0] == name {
+            Some(values[1].clone())
+        } else {
+            None
+        }
+    })
+}
+
+fn nip25_is_reaction(event: &Event) -> bool {
+    event.kind == Kind::Reaction || event.kind == Kind::from(17)
+}
+
+// Per NIP-25 the last `a`, `e` or `r` tag identifies the reacted-to target.
+// Addressable targets are keyed by coordinate so reactions to older versions still count.
+fn nip25_reaction_target(event: &Event) -> Option<String> {
+    nip25_last_tag_value(event, "a")
+        .or_else(|| nip25_last_tag_value(event, "e"))
+        .or_else(|| nip25_last_tag_value(event, "r"))
+}
+
+// An empty reaction is interpreted as a like, same as "+".
+fn nip25_normalize_content(content: &str) -> &str {
+    if content.is_empty() { "+" } else { content }
+}
+
 #[rustler::nif]
-fn nip17_encrypt_dm_nif(secret_key: String, public_key: String, plaintext: String) -> NifResult<String> {
-    use nostr::nips::nip04;
-    use nostr::{SecretKey, PublicKey};
-    let sk = SecretKey::from_str(&secret_key).map_err(|e| rustler::Error::Term(Box::new(e.to_string())))?;
-    let pk = PublicKey::from_str(&public_key).map_err(|e| rustler::Error::Term(Box::new(e.to_string())))?;
-    let ciphertext = nip04::encrypt(&sk, &pk, plaintext)
-        .map_err(|e| rustler::Error::Term(Box::new(e.to_string())))?;
-    Ok(ciphertext)
+fn nip25_create_reaction_nif(
+    secret_key: String,
+    target_json: String,
+    content: String,
+    emoji_url: Option<String>,
+    relay_url: Option<String>,
+) -> NifResult<String> {
+    use nostr::nips::nip01::Coordinate;
+    let keys = to_rustler_error(Keys::parse(&secret_key))?;
+    let target = to_rustler_error(Event::from_json(&target_json))?;
+    let relay_hint = match relay_url {
+        Some(url) => to_rustler_error(RelayUrl::parse(&url))?.to_string(),
+        None => String::new(),
+    };
+
+    let mut tags = vec![
+        to_rustler_error(Tag::parse(["e", &target.id.to_hex(), &relay_hint, &target.pubkey.to_hex()]))?,
+        Tag::public_key(target.pubkey),
+    ];
+    if target.kind.is_addressable() {
+        let identifier = target.tags.identifier().unwrap_or_default();
+        let coordinate = Coordinate::new(target.kind, target.pubkey).identifier(identifier);
+        tags.push(to_rustler_error(Tag::parse(["a", &coordinate.to_string(), &relay_hint]))?);
+    }
+    tags.push(to_rustler_error(Tag::parse(["k", &target.kind.as_u16().to_string()]))?);
+    if let Some(emoji_tag) = nip25_emoji_tag(&content, emoji_url)? {
+        tags.push(emoji_tag);
+    }
+
+    let event = to_rustler_error(EventBuilder::new(Kind::Reaction, content).tags(tags).sign_with_keys(&keys))?;
+    Ok(event.as_json())
 }
 
 #[rustler::nif]
-fn nip17_decrypt_dm_nif(secret_key: String, public_key: String, ciphertext: String) -> NifResult<String> {
-    use nostr::nips::nip04;
-    use nostr::{SecretKey, PublicKey};
-    let sk = SecretKey::from_str(&secret_key).map_err(|e| rustler::Error::Term(Box::new(e.to_string())))?;
-    let pk = PublicKey::from_str(&public_key).map_err(|e| rustler::Error::Term(Box::new(e.to_string())))?;
-    let plaintext = nip04::decrypt(&sk, &pk, ciphertext)
-        .map_err(|e| rustler::Error::Term(Box::new(e.to_string())))?;
-    Ok(plaintext)
-} 
+fn nip25_create_website_reaction_nif(
+    secret_key: String,
+    url: String,
+    content: String,
+    emoji_url: Option<String>,
+) -> NifResult<String> {
+    let keys = to_rustler_error(Keys::parse(&secret_key))?;
+    let url = to_rustler_error(Url::parse(&url))?;
+    let mut tags = vec![to_rustler_error(Tag::parse(["r", url.as_str()]))?];
+    if let Some(emoji_tag) = nip25_emoji_tag(&content, emoji_url)? {
+        tags.push(emoji_tag);
+    }
+    let event = to_rustler_error(EventBuilder::new(Kind::from(17), content).tags(tags).sign_with_keys(&keys))?;
+    Ok(event.as_json())
+}
+
+#[rustler::nif]
+fn nip25_parse_reaction_nif(event_json: String) -> NifResult<String> {
+    let event = to_rustler_error(Event::from_json(&event_json))?;
+    if !nip25_is_reaction(&event) {
+        return Err(rustler::Error::Term(Box::new("Event is not a reaction (kind 7 or 17)".to_string())));
+    }
 
- 
\ No newline at end of file
+    let content = nip25_normalize_content(&event.content);
+    let shortcode = content
+        .strip_prefix(':')
+        .and_then(|s| s.strip_suffix(':'))
+        .filter(|s| !s.is_empty());
+    let emoji_url = shortcode.and_then(|shortcode| {
+        event.tags.iter().find_map(|tag| {
+            let values = tag.as_slice();
+            if values.len() >= 3 && values[0] == "emoji" && values[1] == shortcode {
+                Some(values[2].clone())
+            } else {
+                None
+            }
+        })
+    });
+    let reaction_type = match content {
+        "+" => "like",
+        "-" => "dislike",
+        _ if emoji_url.is_some() => "custom_emoji",
+        _ => "emoji",
+    };
+
+    let result = serde_json::json!({
+        "id": event.id.to_hex(),
+        "pubkey": event.pubkey.to_hex(),
+        "kind": event.kind.as_u16(),
+        "created_at": event.created_at.as_u64(),
+        "content": content,
+        "reaction_type": reaction_type,
+        "shortcode": emoji_url.as_ref().and(shortcode),
+        "emoji_url": emoji_url,
+        "target": nip25_reaction_target(&event),
+        "event_id": nip25_last_tag_value(&event, "e"),
+        "coordinate": nip25_last_tag_value(&event, "a"),
+        "url": nip25_last_tag_value(&event, "r"),
+        "author": nip25_last_tag_value(&event, "p"),
+        "target_kind": nip25_last_tag_value(&event, "k").and_then(|k| k.parse::<u16>().ok())
+    });
+
+    Ok(result.to_string())
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn nip25_aggregate_reactions_nif(events_json: Vec<String>) -> NifResult<String> {
+    use std::collections::{BTreeMap, BTreeSet};
+    let mut reactors: BTreeMap<String, BTreeMap<String, BTreeSet<PublicKey>>> = BTreeMap::new();
+    for event_json in events_json {
+        let event = to_rustler_error(Event::from_json(&event_json))?;
+        // Skip anything that isn't an authentic reaction, otherwise a forged
+        // pubkey could be used to inflate the per-author counts.
+        if !nip25_is_reaction(&event) || event.verify().is_err() {
+            continue;
+        }
+        let Some(target) = nip25_reaction_target(&event) else {
+            continue;
+        };
+        reactors
+            .entry(target)
+            .or_default()
+            .entry(nip25_normalize_content(&event.content).to_string())
+            .or_default()
+            .insert(event.pubkey);
+    }
+
+    let result: BTreeMap<String, BTreeMap<String, usize>> = reactors
+        .into_iter()
+        .map(|(target, by_emoji)| {
+            let counts = by_emoji.into_iter().map(|(emoji, authors)| (emoji, authors.len())).collect();
+            (target, counts)
+        })
+        .collect();
+
+    Ok(serde_json::to_string(&result).unwrap())
+}
+
+// NIP-22: Comments
+
+// Builds a tag from its values, dropping trailing empty hints so that
+// e.g. `["P", pubkey, ""]` is emitted as `["P", pubkey]`.
+fn nip22_tag(values: &[&str]) -> NifResult<Tag> {
+    let len = values.iter().rposition(|v| !v.is_empty()).map_or(0, |i| i + 1);
+    to_rustler_error(Tag::parse(values[..len.max(2)].iter().copied()))
+}
+
+// Scope tags pointing at `event`: uppercase for the root scope, lowercase for the parent.
+fn nip22_event_scope_tags(event: &Event, relay_hint: &str, root: bool) -> NifResult<Vec<Tag>> {
+    let name = |c: &str| if root { c.to_uppercase() } else { c.to_string() };
+    let id = event.id.to_hex();
+    let pubkey = event.pubkey.to_hex();
+    let mut tags = Vec::new();
+    match event.coordinate() {
+        Some(coordinate) => {
+            let coordinate = coordinate.into_owned().to_string();
+            tags.push(nip22_tag(&[&name("a"), &coordinate, relay_hint])?);
+            if !root {
+                tags.push(nip22_tag(&[&name("e"), &id, relay_hint, &pubkey])?);
+            }
+        }
+        None => tags.push(nip22_tag(&[&name("e"), &id, relay_hint, &pubkey])?),
+    }
+    tags.push(nip22_tag(&[&name("k"), &event.kind.as_u16().to_string()])?);
+    tags.push(nip22_tag(&[&name("p"), &pubkey, relay_hint])?);
+    Ok(tags)
+}
+
+// NIP-73 `k` value for an external content identifier.
+fn nip22_external_kind(id: &nostr::nips::nip73::ExternalContentId) -> String {
+    use nostr::nips::nip73::ExternalContentId;
+    match id {
+        ExternalContentId::Url(_) => "web".to_string(),
+        ExternalContentId::Hashtag(_) => "#".to_string(),
+        ExternalContentId::Geohash(_) => "geo".to_string(),
+        ExternalContentId::Book(_) => "isbn".to_string(),
+        ExternalContentId::PodcastFeed(_) => "podcast:guid".to_string(),
+        ExternalContentId::PodcastEpisode(_) => "podcast:item:guid".to_string(),
+        ExternalContentId::PodcastPublisher(_) => "podcast:publisher:guid".to_string(),
+        ExternalContentId::Movie(_) => "isan".to_string(),
+        ExternalContentId::Paper(_) => "doi".to_string(),
+        ExternalContentId::BlockchainTransaction { chain, .. } => format!("{chain}:tx"),
+        ExternalContentId::BlockchainAddress { chain, .. } => format!("{chain}:address"),
+    }
+}
+
+// Reads one comment scope back from the tags; `root` selects the uppercase tags.
+fn nip22_scope_json(event: &Event, root: bool) -> serde_json::Value {
+    let name = |c: &str| if root { c.to_uppercase() } else { c.to_string() };
+    let find = |c: &str| {
+        let tag_name = name(c);
+        event.tags.iter().find_map(|tag| {
+            let values = tag.as_slice();
+            if values.len() >= 2 && values[0] == tag_name {
+                Some(values[1..].to_vec())
+            } else {
+                None
+            }
+        })
+    };
+    let hint = |values: &Option<Vec<String>>, index: usize| {
+        values.as_ref().and_then(|v| v.get(index)).filter(|s| !s.is_empty()).cloned()
+    };
+
+    let e = find("e");
+    let a = find("a");
+    let i = find("i");
+    let p = find("p");
+    let scope_type = if a.is_some() {
+        "address"
+    } else if e.is_some() {
+        "event"
+    } else if i.is_some() {
+        "external"
+    } else {
+        return serde_json::Value::Null;
+    };
+
+    serde_json::json!({
+        "type": scope_type,
+        "event_id": hint(&e, 0),
+        "coordinate": hint(&a, 0),
+        "external": hint(&i, 0),
+        "kind": hint(&find("k"), 0),
+        "pubkey": hint(&p, 0).or_else(|| hint(&e, 2)),
+        "relay_hint": hint(&a, 1).or_else(|| hint(&e, 1)).or_else(|| hint(&i, 1))
+    })
+}
+
+#[rustler::nif]
+fn nip22_create_comment_nif(
+    secret_key: String,
+    content: String,
+    parent_json: String,
+    relay_url: Option<String>,
+) -> NifResult<String> {
+    let keys = to_rustler_error(Keys::parse(&secret_key))?;
+    let parent = to_rustler_error(Event::from_json(&parent_json))?;
+    let relay_hint = match relay_url {
+        Some(url) => to_rustler_error(RelayUrl::parse(&url))?.to_string(),
+        None => String::new(),
+    };
+
+    // Replies to a comment inherit its root scope, anything else becomes the root itself.
+    let mut tags: Vec<Tag> = if parent.kind == Kind::Comment {
+        let root_tags: Vec<Tag> = parent
+            .tags
+            .iter()
+            .filter(|tag| matches!(tag.as_slice().first().map(String::as_str), Some("E" | "A" | "I" | "K" | "P")))
+            .cloned()
+            .collect();
+        if root_tags.is_empty() {
+            return Err(rustler::Error::Term(Box::new("Parent comment has no root scope tags".to_string())));
+        }
+        root_tags
+    } else {
+        nip22_event_scope_tags(&parent, &relay_hint, true)?
+    };
+    tags.extend(nip22_event_scope_tags(&parent, &relay_hint, false)?);
+
+    let event = to_rustler_error(EventBuilder::new(Kind::Comment, content).tags(tags).sign_with_keys(&keys))?;
+    Ok(event.as_json())
+}
+
+#[rustler::nif]
+fn nip22_create_external_comment_nif(
+    secret_key: String,
+    content: String,
+    identifier: String,
+    kind: Option<String>,
+    hint: Option<String>,
+) -> NifResult<String> {
+    use nostr::nips::nip73::ExternalContentId;
+    let keys = to_rustler_error(Keys::parse(&secret_key))?;
+    let external = to_rustler_error(ExternalContentId::from_str(&identifier))?;
+    let kind = kind.unwrap_or_else(|| nip22_external_kind(&external));
+    let identifier = external.to_string();
+    let hint = match hint {
+        Some(hint) => to_rustler_error(Url::parse(&hint))?.to_string(),
+        None => String::new(),
+    };
+
+    let tags = vec![
+        nip22_tag(&["I", &identifier, &hint])?,
+        nip22_tag(&["K", &kind])?,
+        nip22_tag(&["i", &identifier, &hint])?,
+        nip22_tag(&["k", &kind])?,
+    ];
+
+    let event = to_rustler_error(EventBuilder::new(Kind::Comment, content).tags(tags).sign_with_keys(&keys))?;
+    Ok(event.as_json())
+}
+
+#[rustler::nif]
+fn nip22_parse_comment_nif(event_json: String) -> NifResult<String> {
+    let event = to_rustler_error(Event::from_json(&event_json))?;
+    if event.kind != Kind::Comment {
+        return Err(rustler::Error::Term(Box::new("Event is not a comment (kind 1111)".to_string())));
+    }
+
+    let result = serde_json::json!({
+        "id": event.id.to_hex(),
+        "pubkey": event.pubkey.to_hex(),
+        "created_at": event.created_at.as_u64(),
+        "content": event.content,
+        "root": nip22_scope_json(&event, true),
+        "parent": nip22_scope_json(&event, false)
+    });
+
+    Ok(result.to_string())
+}
+
+// NIP-09: Event Deletion Requests
+
+#[rustler::nif]
+fn nip09_create_deletion_event_nif(
+    secret_key: String,
+    event_ids: Vec<String>,
+    coordinates: Vec<String>,
+    kinds: Vec<u16>,
+    reason: Option<String>,
+) -> NifResult<String> {
+    use nostr::nips::nip01::Coordinate;
+    let keys = to_rustler_error(Keys::parse(&secret_key))?;
+    let ids = to_rustler_error(event_ids.iter().map(|id| EventId::from_hex(id)).collect::<Result<Vec<_>, _>>())?;
+    let coordinates = to_rustler_error(coordinates.iter().map(|c| Coordinate::parse(c)).collect::<Result<Vec<_>, _>>())?;
+    if coordinates.iter().any(|c| c.public_key != keys.public_key()) {
+        return Err(rustler::Error::Term(Box::new("Cannot request deletion of another author's coordinate".to_string())));
+    }
+
+    // `k` tags for the explicitly given kinds plus the kinds of the deleted coordinates
+    let mut deleted_kinds: Vec<u16> = kinds;
+    deleted_kinds.extend(coordinates.iter().map(|c| c.kind.as_u16()));
+    deleted_kinds.sort_unstable();
+    deleted_kinds.dedup();
+
+    let mut tags: Vec<Tag> = ids.into_iter().map(Tag::event).collect();
+    tags.extend(coordinates.into_iter().map(|c| Tag::coordinate(c, None)));
+    for kind in deleted_kinds {
+        tags.push(to_rustler_error(Tag::parse(["k", &kind.to_string()]))?);
+    }
+
+    let event = to_rustler_error(
+        EventBuilder::new(Kind::EventDeletion, reason.unwrap_or_default())
+            .tags(tags)
+            .dedup_tags()
+            .sign_with_keys(&keys),
+    )?;
+    Ok(event.as_json())
+}
+
+#[rustler::nif]
+fn nip09_apply_deletion_nif(deletion_json: String, events_json: Vec<String>) -> NifResult<String> {
+    let deletion = to_rustler_error(Event::from_json(&deletion_json))?;
+    if deletion.kind != Kind::EventDeletion {
+        return Err(rustler::Error::Term(Box::new("Event is not a deletion request (kind 5)".to_string())));
+    }
+    to_rustler_error(deletion.verify())?;
+
+    let deleted_ids: Vec<EventId> = deletion.tags.event_ids().copied().collect();
+    let deleted_coordinates: Vec<_> = deletion.tags.coordinates().collect();
+
+    let mut kept: Vec<String> = Vec::new();
+    let mut deleted: Vec<String> = Vec::new();
+    for event_json in events_json {
+        let event = to_rustler_error(Event::from_json(&event_json))?;
+        // Only the author may delete their own events, and deletion requests can't be deleted.
+        let is_deleted = event.pubkey == deletion.pubkey
+            && event.kind != Kind::EventDeletion
+            && (deleted_ids.contains(&event.id)
+                || (event.created_at <= deletion.created_at
+                    && event.coordinate().is_some_and(|coordinate| {
+                        let coordinate = coordinate.into_owned();
+                        deleted_coordinates.iter().any(|c| **c == coordinate)
+                    })));
+        if is_deleted {
+            deleted.push(event.id.to_hex());
+        } else {
+            kept.push(event_json);
+        }
+    }
+
+    let result = serde_json::json!({
+        "events": kept,
+        "deleted_ids": deleted
+    });
+
+    Ok(result.to_string())
+}
+
+// NIP-23: Long-form Content
+
+#[rustler::nif]
+fn nip23_create_long_form_nif(secret_key: String, content: String, opts_json: String, draft: bool) -> NifResult<String> {
+    let keys = to_rustler_error(Keys::parse(&secret_key))?;
+    let opts: serde_json::Value = to_rustler_error(serde_json::from_str(&opts_json))?;
+
+    let identifier = match opts["identifier"].as_str() {
+        Some(identifier) if !identifier.is_empty() => identifier,
+        _ => return Err(rustler::Error::Term(Box::new("Long-form content requires an identifier (d tag)".to_string()))),
+    };
+    let mut tags = vec![Tag::identifier(identifier)];
+    if let Some(title) = opts["title"].as_str() {
+        tags.push(Tag::from_standardized(TagStandard::Title(title.to_string())));
+    }
+    if let Some(summary) = opts["summary"].as_str() {
+        tags.push(Tag::from_standardized(TagStandard::Summary(summary.to_string())));
+    }
+    if let Some(image) = opts["image"].as_str() {
+        let url = to_rustler_error(Url::parse(image))?;
+        tags.push(Tag::image(url, None));
+    }
+    if let Some(published_at) = opts["published_at"].as_u64() {
+        tags.push(Tag::from_standardized(TagStandard::PublishedAt(Timestamp::from(published_at))));
+    }
+    if let Some(hashtags) = opts["hashtags"].as_array() {
+        tags.extend(hashtags.iter().filter_map(|h| h.as_str()).map(Tag::hashtag));
+    }
+
+    let builder = if draft {
+        EventBuilder::new(Kind::from(30024), content)
+    } else {
+        EventBuilder::long_form_text_note(content)
+    };
+    let event = to_rustler_error(builder.tags(tags).sign_with_keys(&keys))?;
+    Ok(event.as_json())
+}
+
+#[rustler::nif]
+fn nip23_parse_long_form_nif(event_json: String, relays: Vec<String>) -> NifResult<String> {
+    use nostr::nips::nip19::Nip19Coordinate;
+    let event = to_rustler_error(Event::from_json(&event_json))?;
+    let draft = event.kind == Kind::from(30024);
+    if event.kind != Kind::LongFormTextNote && !draft {
+        return Err(rustler::Error::Term(Box::new("Event is not long-form content (kind 30023 or 30024)".to_string())));
+    }
+
+    let coordinate = event.coordinate().map(|c| c.into_owned()).expect("long-form kinds are addressable");
+    let naddr = to_rustler_error(Nip19Coordinate::new(coordinate.clone(), relays.iter().map(String::as_str)))?;
+    let naddr = to_rustler_error(naddr.to_bech32())?;
+
+    let mut title = None;
+    let mut summary = None;
+    let mut image = None;
+    let mut published_at = None;
+    for tag in event.tags.iter() {
+        match tag.as_standardized() {
+            Some(TagStandard::Title(value)) => title = Some(value.clone()),
+            Some(TagStandard::Summary(value)) => summary = Some(value.clone()),
+            Some(TagStandard::Image(url, _)) => image = Some(url.to_string()),
+            Some(TagStandard::PublishedAt(timestamp)) => published_at = Some(timestamp.as_u64()),
+            _ => {}
+        }
+    }
+
+    let result = serde_json::json!({
+        "id": event.id.to_hex(),
+        "pubkey": event.pubkey.to_hex(),
+        "created_at": event.created_at.as_u64(),
+        "kind": event.kind.as_u16(),
+        "draft": draft,
+        "identifier": coordinate.identifier,
+        "title": title,
+        "summary": summary,
+        "image": image,
+        "published_at": published_at,
+        "hashtags": event.tags.hashtags().collect::<Vec<&str>>(),
+        "content": event.content,
+        "coordinate": coordinate.to_string(),
+        "naddr": naddr
+    });
+
+    Ok(result.to_string())
+}
+
+// NIP-01/NIP-24: Profile Metadata (kind 0)
+
+fn metadata_validate(metadata: &Metadata) -> NifResult<()> {
+    let urls = [("website", &metadata.website), ("picture", &metadata.picture), ("banner", &metadata.banner)];
+    for (field, url) in urls {
+        if let Some(url) = url {
+            Url::parse(url).map_err(|e| rustler::Error::Term(Box::new(format!("Invalid {field} URL: {e}"))))?;
+        }
+    }
+    if let Some(bot) = metadata.custom.get("bot") {
+        if !bot.is_boolean() {
+            return Err(rustler::Error::Term(Box::new("Invalid bot field: must be a boolean".to_string())));
+        }
+    }
+    if let Some(birthday) = metadata.custom.get("birthday") {
+        let valid = birthday.as_object().is_some_and(|fields| {
+            fields.iter().all(|(key, value)| {
+                let value = value.as_u64();
+                match key.as_str() {
+                    "year" => value.is_some(),
+                    "month" => value.is_some_and(|m| (1..=12).contains(&m)),
+                    "day" => value.is_some_and(|d| (1..=31).contains(&d)),
+                    _ => false,
+                }
+            })
+        });
+        if !valid {
+            return Err(rustler::Error::Term(Box::new("Invalid birthday field: must be an object with year, month and/or day".to_string())));
+        }
+    }
+    Ok(())
+}
+
+#[rustler::nif]
+fn metadata_create_event_nif(secret_key: String, metadata_json: String) -> NifResult<String> {
+    let keys = to_rustler_error(Keys::parse(&secret_key))?;
+    let metadata = to_rustler_error(Metadata::from_json(&metadata_json))?;
+    metadata_validate(&metadata)?;
+    let event = to_rustler_error(EventBuilder::metadata(&metadata).sign_with_keys(&keys))?;
+    Ok(event.as_json())
+}
+
+#[rustler::nif]
+fn metadata_parse_nif(event_json: String) -> NifResult<String> {
+    let event = to_rustler_error(Event::from_json(&event_json))?;
+    if event.kind != Kind::Metadata {
+        return Err(rustler::Error::Term(Box::new("Event is not profile metadata (kind 0)".to_string())));
+    }
+    let mut metadata = to_rustler_error(Metadata::from_json(&event.content))?;
+    let bot = metadata.custom.remove("bot");
+    let birthday = metadata.custom.remove("birthday");
+
+    let result = serde_json::json!({
+        "pubkey": event.pubkey.to_hex(),
+        "created_at": event.created_at.as_u64(),
+        "name": metadata.name,
+        "display_name": metadata.display_name,
+        "about": metadata.about,
+        "picture": metadata.picture,
+        "banner": metadata.banner,
+        "website": metadata.website,
+        "nip05": metadata.nip05,
+        "lud06": metadata.lud06,
+        "lud16": metadata.lud16,
+        "bot": bot.and_then(|b| b.as_bool()),
+        "birthday": birthday,
+        "custom": metadata.custom
+    });
+
+    Ok(result.to_string())
+}
+
+#[rustler::nif]
+fn metadata_merge_nif(existing_json: String, update_json: String) -> NifResult<String> {
+    let mut merged: serde_json::Map<String, serde_json::Value> = to_rustler_error(serde_json::from_str(&existing_json))?;
+    let update: serde_json::Map<String, serde_json::Value> = to_rustler_error(serde_json::from_str(&update_json))?;
+    // Fields not mentioned in the update are kept as-is; `null` removes a field.
+    for (field, value) in update {
+        if value.is_null() {
+            merged.remove(&field);
+        } else {
+            merged.insert(field, value);
+        }
+    }
+    let merged = serde_json::Value::Object(merged).to_string();
+    let metadata = to_rustler_error(Metadata::from_json(&merged))?;
+    metadata_validate(&metadata)?;
+    Ok(merged)
+}
+
+// NIP-05: DNS-based identifiers
+
+// NIP-05 local parts are restricted to `a-z0-9-_.`
+fn nip05_valid_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '-' | '_' | '.'))
+}
+
+// Splits `name@domain` (or a bare `domain`, meaning `_@domain`) into its lowercased
+// parts and the `/.well-known/nostr.json` URL to query.
+fn nip05_split(identifier: &str) -> NifResult<(String, String, String)> {
+    let identifier = identifier.trim().to_lowercase();
+    let (name, domain) = match identifier.split_once('@') {
+        Some((name, domain)) => (name.to_string(), domain.to_string()),
+        None => ("_".to_string(), identifier),
+    };
+    if !nip05_valid_name(&name) {
+        return Err(rustler::Error::Term(Box::new(format!("Invalid NIP-05 name: {name}"))));
+    }
+    let url = format!("https://{domain}/.well-known/nostr.json?name={name}");
+    let valid_domain = !domain.is_empty()
+        && !domain.contains(['/', '?', '#', '@'])
+        && Url::parse(&url).is_ok_and(|u| u.host_str() == Some(domain.as_str()));
+    if !valid_domain {
+        return Err(rustler::Error::Term(Box::new(format!("Invalid NIP-05 domain: {domain}"))));
+    }
+    Ok((name, domain, url))
+}
+
+fn nip05_relays_for(json: &serde_json::Value, field: &str, public_key: &PublicKey) -> Vec<String> {
+    json.get(field)
+        .and_then(|relays| relays.get(public_key.to_hex()))
+        .and_then(|relays| relays.as_array())
+        .map(|relays| {
+            relays
+                .iter()
+                .filter_map(|r| r.as_str())
+                .filter_map(|r| RelayUrl::parse(r).ok())
+                .map(|r| r.to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[rustler::nif]
+fn nip05_parse_nif(identifier: String) -> NifResult<String> {
+    let (name, domain, url) = nip05_split(&identifier)?;
+    let result = serde_json::json!({
+        "name": name,
+        "domain": domain,
+        "url": url
+    });
+    Ok(result.to_string())
+}
+
+#[rustler::nif]
+fn nip05_profile_from_json_nif(identifier: String, nostr_json: String) -> NifResult<String> {
+    let (name, _, _) = nip05_split(&identifier)?;
+    let json: serde_json::Value = to_rustler_error(serde_json::from_str(&nostr_json))?;
+    let public_key = json
+        .get("names")
+        .and_then(|names| names.get(&name))
+        .and_then(|pubkey| pubkey.as_str())
+        .ok_or_else(|| rustler::Error::Term(Box::new(format!("Name not found in nostr.json: {name}"))))?;
+    let public_key = to_rustler_error(PublicKey::from_hex(public_key))?;
+
+    let result = serde_json::json!({
+        "public_key": public_key.to_hex(),
+        "relays": nip05_relays_for(&json, "relays", &public_key),
+        "nip46": nip05_relays_for(&json, "nip46", &public_key)
+    });
+    Ok(result.to_string())
+}
+
+#[rustler::nif]
+fn nip05_verify_nif(identifier: String, public_key: String, nostr_json: String) -> NifResult<bool> {
+    let (name, _, _) = nip05_split(&identifier)?;
+    let public_key = to_rustler_error(PublicKey::parse(&public_key))?;
+    let json: serde_json::Value = to_rustler_error(serde_json::from_str(&nostr_json))?;
+    let verified = json
+        .get("names")
+        .and_then(|names| names.get(&name))
+        .and_then(|pubkey| pubkey.as_str())
+        .and_then(|pubkey| PublicKey::from_hex(pubkey).ok())
+        .is_some_and(|pubkey| pubkey == public_key);
+    Ok(verified)
+}
+
+#[rustler::nif]
+fn nip05_build_document_nif(entries: Vec<(String, String, Vec<String>)>) -> NifResult<String> {
+    let mut names = serde_json::Map::new();
+    let mut relays = serde_json::Map::new();
+    for (name, public_key, relay_urls) in entries {
+        let name = name.to_lowercase();
+        if !nip05_valid_name(&name) {
+            return Err(rustler::Error::Term(Box::new(format!("Invalid NIP-05 name: {name}"))));
+        }
+        let public_key = to_rustler_error(PublicKey::parse(&public_key))?.to_hex();
+        if !relay_urls.is_empty() {
+            let relay_urls = to_rustler_error(relay_urls.iter().map(|r| RelayUrl::parse(r).map(|r| r.to_string())).collect::<Result<Vec<_>, _>>())?;
+            relays.insert(public_key.clone(), serde_json::json!(relay_urls));
+        }
+        names.insert(name, serde_json::Value::String(public_key));
+    }
+
+    let mut document = serde_json::Map::new();
+    document.insert("names".to_string(), serde_json::Value::Object(names));
+    if !relays.is_empty() {
+        document.insert("relays".to_string(), serde_json::Value::Object(relays));
+    }
+    Ok(serde_json::Value::Object(document).to_string())
+}
+
+// NIP-59: Gift Wrap
+//
+// Seals and gift wraps are built synchronously with NIP-44 rather than through the
+// async `NostrSigner` helpers of the nostr crate.
+
+fn nip59_seal(sender: &Keys, receiver: &PublicKey, rumor: &UnsignedEvent, created_at: Timestamp) -> NifResult<Event> {
+    use nostr::nips::nip44::{self, Version};
+    let content = to_rustler_error(nip44::encrypt(sender.secret_key(), receiver, rumor.as_json(), Version::V2))?;
+    to_rustler_error(
+        EventBuilder::new(Kind::Seal, content)
+            .custom_created_at(created_at)
+            .sign_with_keys(sender),
+    )
+}
+
+fn nip59_gift_wrap(seal: &Event, receiver: &PublicKey, extra_tags: Vec<Tag>, created_at: Timestamp) -> NifResult<Event> {
+    use nostr::nips::nip44::{self, Version};
+    if seal.kind != Kind::Seal {
+        return Err(rustler::Error::Term(Box::new("Event is not a seal (kind 13)".to_string())));
+    }
+    let ephemeral = Keys::generate();
+    let content = to_rustler_error(nip44::encrypt(ephemeral.secret_key(), receiver, seal.as_json(), Version::V2))?;
+    let mut tags = extra_tags;
+    tags.push(Tag::public_key(*receiver));
+    to_rustler_error(
+        EventBuilder::new(Kind::GiftWrap, content)
+            .tags(tags)
+            .custom_created_at(created_at)
+            .sign_with_keys(&ephemeral),
+    )
+}
+
+// Randomized `created_at` for seals and gift wraps. `tweak` is the (min, max) number
+// of seconds subtracted from now; NIP-59 recommends up to two days.
+fn nip59_created_at(tweak: Option<(u64, u64)>) -> NifResult<Timestamp> {
+    use nostr::nips::nip59::RANGE_RANDOM_TIMESTAMP_TWEAK;
+    match tweak {
+        None => Ok(Timestamp::tweaked(RANGE_RANDOM_TIMESTAMP_TWEAK)),
+        Some((min, max)) if min == max => Ok(Timestamp::now() - min),
+        Some((min, max)) if min < max => Ok(Timestamp::tweaked(min..max)),
+        Some(_) => Err(rustler::Error::Term(Box::new("Invalid timestamp tweak range".to_string()))),
+    }
+}
+
+// Opens a gift wrap and its seal, returning both. Fails unless the seal is signed
+// by the same key that authored the rumor, otherwise the sender could be spoofed.
+fn nip59_unwrap(receiver: &Keys, gift_wrap: &Event) -> NifResult<(Event, UnsignedEvent)> {
+    use nostr::nips::nip44;
+    if gift_wrap.kind != Kind::GiftWrap {
+        return Err(rustler::Error::Term(Box::new("Event is not a gift wrap (kind 1059)".to_string())));
+    }
+    to_rustler_error(gift_wrap.verify())?;
+    let seal_json = to_rustler_error(nip44::decrypt(receiver.secret_key(), &gift_wrap.pubkey, &gift_wrap.content))?;
+    let seal = to_rustler_error(Event::from_json(&seal_json))?;
+    if seal.kind != Kind::Seal {
+        return Err(rustler::Error::Term(Box::new("Gift wrap does not contain a seal (kind 13)".to_string())));
+    }
+    to_rustler_error(seal.verify())?;
+    let rumor_json = to_rustler_error(nip44::decrypt(receiver.secret_key(), &seal.pubkey, &seal.content))?;
+    let mut rumor = to_rustler_error(UnsignedEvent::from_json(&rumor_json))?;
+    if rumor.pubkey != seal.pubkey {
+        return Err(rustler::Error::Term(Box::new("Seal author does not match rumor pubkey".to_string())));
+    }
+    if rumor.id.is_some() {
+        to_rustler_error(rumor.verify_id())?;
+    } else {
+        rumor.ensure_id();
+    }
+    Ok((seal, rumor))
+}
+
+#[rustler::nif]
+fn nip59_seal_nif(
+    rumor_json: String,
+    secret_key: String,
+    receiver: String,
+    tweak: Option<(u64, u64)>,
+) -> NifResult<String> {
+    let keys = to_rustler_error(Keys::parse(&secret_key))?;
+    let receiver = to_rustler_error(PublicKey::parse(&receiver))?;
+    let mut rumor = to_rustler_error(UnsignedEvent::from_json(&rumor_json))?;
+    if rumor.pubkey != keys.public_key() {
+        return Err(rustler::Error::Term(Box::new("Rumor pubkey does not match sender keys".to_string())));
+    }
+    // Parsing as an unsigned event drops any `sig`, so a signed event is never sealed.
+    // A supplied id must match, or the receiver would fail to unwrap the rumor.
+    if rumor.id.is_some() {
+        to_rustler_error(rumor.verify_id())?;
+    } else {
+        rumor.ensure_id();
+    }
+    let seal = nip59_seal(&keys, &receiver, &rumor, nip59_created_at(tweak)?)?;
+    Ok(seal.as_json())
+}
+
+#[rustler::nif]
+fn nip59_gift_wrap_nif(
+    seal_json: String,
+    receiver: String,
+    extra_tags: Vec<Vec<String>>,
+    expiration: Option<u64>,
+    tweak: Option<(u64, u64)>,
+) -> NifResult<String> {
+    let seal = to_rustler_error(Event::from_json(&seal_json))?;
+    to_rustler_error(seal.verify())?;
+    let receiver = to_rustler_error(PublicKey::parse(&receiver))?;
+    let mut tags = to_rustler_error(extra_tags.into_iter().map(Tag::parse).collect::<Result<Vec<_>, _>>())?;
+    if let Some(expiration) = expiration {
+        tags.push(Tag::expiration(Timestamp::from(expiration)));
+    }
+    let gift_wrap = nip59_gift_wrap(&seal, &receiver, tags, nip59_created_at(tweak)?)?;
+    Ok(gift_wrap.as_json())
+}
+
+#[rustler::nif]
+fn nip59_unwrap_nif(gift_wrap_json: String, secret_key: String) -> NifResult<String> {
+    let keys = to_rustler_error(Keys::parse(&secret_key))?;
+    let gift_wrap = to_rustler_error(Event::from_json(&gift_wrap_json))?;
+    let (seal, rumor) = nip59_unwrap(&keys, &gift_wrap)?;
+    let result = serde_json::json!({
+        "sender": seal.pubkey.to_hex(),
+        "seal": seal.as_json(),
+        "rumor": rumor.as_json()
+    });
+    Ok(result.to_string())
+}
+
+// NIP-17: Private Direct Messages
+
+#[rustler::nif]
+fn nip17_send_private_msg_nif(
+    secret_key: String,
+    receivers: Vec<String>,
+    message: String,
+    subject: Option<String>,
+    reply_to: Option<String>,
+) -> NifResult<String> {
+    let keys = to_rustler_error(Keys::parse(&secret_key))?;
+    let receivers = to_rustler_error(receivers.iter().map(|r| PublicKey::parse(r)).collect::<Result<Vec<_>, _>>())?;
+    if receivers.is_empty() {
+        return Err(rustler::Error::Term(Box::new("At least one receiver is required".to_string())));
+    }
+
+    let mut tags: Vec<Tag> = receivers.iter().copied().map(Tag::public_key).collect();
+    if let Some(subject) = subject {
+        tags.push(to_rustler_error(Tag::parse(["subject", &subject]))?);
+    }
+    if let Some(reply_to) = reply_to {
+        tags.push(Tag::event(to_rustler_error(EventId::from_hex(&reply_to))?));
+    }
+    let rumor = EventBuilder::new(Kind::PrivateDirectMessage, message)
+        .tags(tags)
+        .build(keys.public_key());
+
+    // One gift wrap per receiver, plus one for the sender's own copy.
+    let mut wrap_for = receivers.clone();
+    if !wrap_for.contains(&keys.public_key()) {
+        wrap_for.push(keys.public_key());
+    }
+    let mut gift_wraps = Vec::with_capacity(wrap_for.len());
+    for receiver in wrap_for {
+        let seal = nip59_seal(&keys, &receiver, &rumor, nip59_created_at(None)?)?;
+        let gift_wrap = nip59_gift_wrap(&seal, &receiver, Vec::new(), nip59_created_at(None)?)?;
+        gift_wraps.push(serde_json::json!({
+            "receiver": receiver.to_hex(),
+            "event": gift_wrap.as_json()
+        }));
+    }
+
+    let result = serde_json::json!({
+        "rumor_id": rumor.id.map(|id| id.to_hex()),
+        "gift_wraps": gift_wraps
+    });
+    Ok(result.to_string())
+}
+
+#[rustler::nif]
+fn nip17_unwrap_private_msg_nif(secret_key: String, gift_wrap_json: String) -> NifResult<String> {
+    let keys = to_rustler_error(Keys::parse(&secret_key))?;
+    let gift_wrap = to_rustler_error(Event::from_json(&gift_wrap_json))?;
+    let (_seal, rumor) = nip59_unwrap(&keys, &gift_wrap)?;
+    if rumor.kind != Kind::PrivateDirectMessage && rumor.kind != Kind::from(15) {
+        return Err(rustler::Error::Term(Box::new(format!(
+            "Rumor is not a private direct message (kind {})",
+            rumor.kind.as_u16()
+        ))));
+    }
+
+    let mut participants: Vec<String> = vec![rumor.pubkey.to_hex()];
+    for public_key in rumor.tags.public_keys() {
+        let public_key = public_key.to_hex();
+        if !participants.contains(&public_key) {
+            participants.push(public_key);
+        }
+    }
+    let find = |name: &str| {
+        rumor.tags.iter().find_map(|tag| {
+            let values = tag.as_slice();
+            if values.len() >= 2 && values[0] == name {
+                Some(values[1].clone())
+            } else {
+                None
+            }
+        })
+    };
+
+    let result = serde_json::json!({
+        "sender": rumor.pubkey.to_hex(),
+        "message": rumor.content,
+        "kind": rumor.kind.as_u16(),
+        "created_at": rumor.created_at.as_u64(),
+        "participants": participants,
+        "subject": find("subject"),
+        "reply_to": find("e"),
+        "rumor": serde_json::from_str::<serde_json::Value>(&rumor.as_json()).unwrap()
+    });
+    Ok(result.to_string())
+}
+
+// NIP-04: Encrypted Direct Message (legacy)
+
+#[rustler::nif]
+fn nip04_encrypt_nif(secret_key: String, public_key: String, plaintext: String) -> NifResult<String> {
+    use nostr::nips::nip04;
+    let sk = to_rustler_error(SecretKey::from_str(&secret_key))?;
+    let pk = to_rustler_error(PublicKey::from_str(&public_key))?;
+    to_rustler_error(nip04::encrypt(&sk, &pk, plaintext))
+}
+
+#[rustler::nif]
+fn nip04_decrypt_nif(secret_key: String, public_key: String, ciphertext: String) -> NifResult<String> {
+    use nostr::nips::nip04;
+    let sk = to_rustler_error(SecretKey::from_str(&secret_key))?;
+    let pk = to_rustler_error(PublicKey::from_str(&public_key))?;
+    to_rustler_error(nip04::decrypt(&sk, &pk, ciphertext))
+}
+
+#[rustler::nif]
+fn nip04_create_dm_event_nif(
+    secret_key: String,
+    receiver: String,
+    message: String,
+    reply_to: Option<String>,
+) -> NifResult<String> {
+    use nostr::nips::nip04;
+    let keys = to_rustler_error(Keys::parse(&secret_key))?;
+    let receiver = to_rustler_error(PublicKey::parse(&receiver))?;
+    let content = to_rustler_error(nip04::encrypt(keys.secret_key(), &receiver, message))?;
+    let mut tags = vec![Tag::public_key(receiver)];
+    if let Some(reply_to) = reply_to {
+        tags.push(Tag::event(to_rustler_error(EventId::from_hex(&reply_to))?));
+    }
+    let event = to_rustler_error(
+        EventBuilder::new(Kind::EncryptedDirectMessage, content)
+            .tags(tags)
+            .allow_self_tagging()
+            .sign_with_keys(&keys),
+    )?;
+    Ok(event.as_json())
+}
+
+// Decrypts a kind 4 event for either participant: the author decrypts with the `p`
+// tagged receiver, the receiver with the author.
+#[rustler::nif]
+fn nip04_decrypt_dm_event_nif(secret_key: String, event_json: String) -> NifResult<String> {
+    use nostr::nips::nip04;
+    let keys = to_rustler_error(Keys::parse(&secret_key))?;
+    let event = to_rustler_error(Event::from_json(&event_json))?;
+    if event.kind != Kind::EncryptedDirectMessage {
+        return Err(rustler::Error::Term(Box::new("Event is not an encrypted direct message (kind 4)".to_string())));
+    }
+    to_rustler_error(event.verify())?;
+    let receiver = event
+        .tags
+        .public_keys()
+        .next()
+        .copied()
+        .ok_or_else(|| rustler::Error::Term(Box::new("Missing p tag".to_string())))?;
+    let peer = if keys.public_key() == event.pubkey {
+        receiver
+    } else if keys.public_key() == receiver {
+        event.pubkey
+    } else {
+        return Err(rustler::Error::Term(Box::new("Keys are not a participant of this message".to_string())));
+    };
+    let plaintext = to_rustler_error(nip04::decrypt(keys.secret_key(), &peer, &event.content))?;
+    let result = serde_json::json!({
+        "id": event.id.to_hex(),
+        "sender": event.pubkey.to_hex(),
+        "receiver": receiver.to_hex(),
+        "peer": peer.to_hex(),
+        "created_at": event.created_at.as_u64(),
+        "plaintext": plaintext
+    });
+    Ok(result.to_string())
+}
+
+// NIP-49: Private Key Encryption
+//
+// scrypt is deliberately slow (log_n 16 takes ~100ms, 20+ takes seconds), so these
+// run on a dirty CPU scheduler.
+
+// scrypt needs 128 * r * 2^log_n bytes (r = 8), so 2^20 is already 1 GiB. log_n comes from
+// untrusted ncryptsec strings and a failed allocation would abort the VM.
+const NIP49_MAX_LOG_N: u8 = 20;
+
+fn nip49_check_log_n(log_n: u8) -> NifResult<()> {
+    if log_n > NIP49_MAX_LOG_N {
+        return Err(rustler::Error::Term(Box::new(format!("log_n {} exceeds the maximum of {}", log_n, NIP49_MAX_LOG_N))));
+    }
+    Ok(())
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn nip49_encrypt_nif(secret_key: String, password: String, log_n: u8, key_security: u8) -> NifResult<String> {
+    use nostr::nips::nip49::{EncryptedSecretKey, KeySecurity};
+    let keys = to_rustler_error(Keys::parse(&secret_key))?;
+    let key_security = to_rustler_error(KeySecurity::try_from(key_security))?;
+    nip49_check_log_n(log_n)?;
+    let encrypted = to_rustler_error(EncryptedSecretKey::new(keys.secret_key(), &password, log_n, key_security))?;
+    to_rustler_error(encrypted.to_bech32())
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn nip49_decrypt_nif(ncryptsec: String, password: String) -> NifResult<String> {
+    use nostr::nips::nip49::EncryptedSecretKey;
+    let encrypted = to_rustler_error(EncryptedSecretKey::from_bech32(&ncryptsec))?;
+    nip49_check_log_n(encrypted.log_n())?;
+    let secret_key = to_rustler_error(encrypted.decrypt(&password))?;
+    let keys = Keys::new(secret_key);
+    let result = serde_json::json!({
+        "secret_key": keys.secret_key().to_secret_hex(),
+        "public_key": keys.public_key().to_hex(),
+        "log_n": encrypted.log_n(),
+        "key_security": encrypted.key_security() as u8
+    });
+    Ok(result.to_string())
+}
+
+// BOLT11: Lightning invoices
+//
+// Offline decoder used by zaps and NWC. Unknown tagged fields are skipped, as required
+// by BOLT11, and the signature is checked against the payee node key.
+
+// Plain bech32 caps strings at 1023 characters, but invoices with route hints can be
+// longer. Same checksum, with the longer code length lightning-invoice uses.
+enum Bolt11Bech32 {}
+
+impl bech32::Checksum for Bolt11Bech32 {
+    type MidstateRepr = u32;
+    const CODE_LENGTH: usize = 7089;
+    const CHECKSUM_LENGTH: usize = 6;
+    const GENERATOR_SH: [u32; 5] = [0x3b6a_57b2, 0x2650_8e6d, 0x1ea1_19fa, 0x3d42_33dd, 0x2a14_62b3];
+    const TARGET_RESIDUE: u32 = 1;
+}
+
+struct Bolt11Invoice {
+    network: &'static str,
+    amount_msat: Option<u64>,
+    timestamp: u64,
+    payment_hash: String,
+    payment_secret: Option<String>,
+    description: Option<String>,
+    description_hash: Option<String>,
+    expiry: u64,
+    min_final_cltv_expiry: u64,
+    payee: String,
+}
+
+// Packs 5-bit groups into bytes, dropping any incomplete trailing byte.
+fn bolt11_to_bytes(groups: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(groups.len() * 5 / 8);
+    let mut acc: u32 = 0;
+    let mut bits = 0;
+    for group in groups {
+        acc = (acc << 5) | *group as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((acc >> bits) as u8);
+            acc &= (1 << bits) - 1;
+        }
+    }
+    bytes
+}
+
+fn bolt11_to_u64(groups: &[u8]) -> u64 {
+    groups.iter().fold(0u64, |acc, group| (acc << 5) | *group as u64)
+}
+
+// Splits the human-readable part (e.g. `lnbc2500u`) into network and amount in msat.
+fn bolt11_parse_hrp(hrp: &str) -> Result<(&'static str, Option<u64>), String> {
+    let rest = hrp.strip_prefix("ln").ok_or("Invalid invoice prefix")?;
+    let split = rest.find(|c: char| c.is_ascii_digit()).unwrap_or(rest.len());
+    let (currency, amount) = rest.split_at(split);
+    let network = match currency {
+        "bc" => "bitcoin",
+        "tb" => "testnet",
+        "tbs" => "signet",
+        "bcrt" => "regtest",
+        _ => return Err(format!("Unknown invoice currency: {}", currency)),
+    };
+    if amount.is_empty() {
+        return Ok((network, None));
+    }
+    let (digits, multiplier) = match amount.char_indices().last() {
+        Some((i, c)) if !c.is_ascii_digit() => (&amount[..i], Some(c)),
+        _ => (amount, None),
+    };
+    let value: u64 = digits.parse().map_err(|_| "Invalid invoice amount".to_string())?;
+    let msat = match multiplier {
+        None => value.checked_mul(100_000_000_000),
+        Some('m') => value.checked_mul(100_000_000),
+        Some('u') => value.checked_mul(100_000),
+        Some('n') => value.checked_mul(100),
+        Some('p') if value.is_multiple_of(10) => Some(value / 10),
+        Some('p') => return Err("Invoice amount is not a whole number of millisatoshis".to_string()),
+        Some(c) => return Err(format!("Unknown invoice amount multiplier: {}", c)),
+    };
+    Ok((network, Some(msat.ok_or("Invalid invoice amount")?)))
+}
+
+fn bolt11_decode(invoice: &str) -> Result<Bolt11Invoice, String> {
+    use bech32::primitives::decode::CheckedHrpstring;
+    use bech32::Fe32;
+    use bitcoin::hashes::{sha256, Hash};
+    use bitcoin::secp256k1::ecdsa::{RecoverableSignature, RecoveryId};
+    use bitcoin::secp256k1::{Message, PublicKey as NodeKey, Secp256k1};
+    let invoice = invoice.trim().to_lowercase();
+    let invoice = invoice.strip_prefix("lightning:").unwrap_or(&invoice);
+    let checked = CheckedHrpstring::new::<Bolt11Bech32>(invoice).map_err(|e| e.to_string())?;
+    let hrp = checked.hrp().to_lowercase();
+    let (network, amount_msat) = bolt11_parse_hrp(&hrp)?;
+    let data: Vec<u8> = checked
+        .data_part_ascii_no_checksum()
+        .iter()
+        .map(|c| Fe32::from_char(*c as char).map(|fe| fe.to_u8()))
+        .collect::<Result<_, _>>()
+        .map_err(|e| e.to_string())?;
+    // 7 groups of timestamp, then tagged fields, then a 65-byte signature (104 groups).
+    if data.len() < 7 + 104 {
+        return Err("Invoice is too short".to_string());
+    }
+    let (fields, signature) = data.split_at(data.len() - 104);
+
+    let mut payment_hash = None;
+    let mut payee = None;
+    let mut decoded = Bolt11Invoice {
+        network,
+        amount_msat,
+        timestamp: bolt11_to_u64(&fields[..7]),
+        payment_hash: String::new(),
+        payment_secret: None,
+        description: None,
+        description_hash: None,
+        expiry: 3600,
+        min_final_cltv_expiry: 18,
+        payee: String::new(),
+    };
+    let mut rest = &fields[7..];
+    while rest.len() >= 3 {
+        let tag = rest[0];
+        let len = bolt11_to_u64(&rest[1..3]) as usize;
+        let value = rest.get(3..3 + len).ok_or("Invalid tagged field length")?;
+        match (Fe32::try_from(tag).map_err(|e| e.to_string())?.to_char(), len) {
+            ('p', 52) => payment_hash = Some(hex::encode(bolt11_to_bytes(value))),
+            ('s', 52) => decoded.payment_secret = Some(hex::encode(bolt11_to_bytes(value))),
+            ('h', 52) => decoded.description_hash = Some(hex::encode(bolt11_to_bytes(value))),
+            ('n', 53) => payee = Some(NodeKey::from_slice(&bolt11_to_bytes(value)).map_err(|e| e.to_string())?),
+            ('d', _) => {
+                decoded.description =
+                    Some(String::from_utf8(bolt11_to_bytes(value)).map_err(|_| "Invalid invoice description")?)
+            }
+            ('x', _) => decoded.expiry = bolt11_to_u64(value),
+            ('c', _) => decoded.min_final_cltv_expiry = bolt11_to_u64(value),
+            _ => {}
+        }
+        rest = &rest[3 + len..];
+    }
+    decoded.payment_hash = payment_hash.ok_or("Invoice has no payment hash")?;
+
+    // The signed message is the hrp followed by the data part, zero-padded to whole bytes.
+    let mut padded = fields.to_vec();
+    padded.resize(fields.len().div_ceil(8) * 8, 0);
+    let mut preimage = hrp.into_bytes();
+    preimage.extend(bolt11_to_bytes(&padded));
+    let message = Message::from_slice(sha256::Hash::hash(&preimage).as_byte_array()).map_err(|e| e.to_string())?;
+    let signature = bolt11_to_bytes(signature);
+    let signature = RecoveryId::from_i32(signature[64] as i32)
+        .and_then(|recovery_id| RecoverableSignature::from_compact(&signature[..64], recovery_id))
+        .map_err(|_| "Invalid invoice signature")?;
+    let secp = Secp256k1::verification_only();
+    let payee = match payee {
+        Some(payee) => {
+            let mut standard = signature.to_standard();
+            standard.normalize_s();
+            secp.verify_ecdsa(&message, &standard, &payee).map_err(|_| "Invalid invoice signature")?;
+            payee
+        }
+        None => secp.recover_ecdsa(&message, &signature).map_err(|_| "Invalid invoice signature")?,
+    };
+    decoded.payee = payee.to_string();
+    Ok(decoded)
+}
+
+#[rustler::nif]
+fn bolt11_decode_nif(invoice: String) -> NifResult<String> {
+    let invoice = bolt11_decode(&invoice).map_err(|e| rustler::Error::Term(Box::new(e)))?;
+    let result = serde_json::json!({
+        "network": invoice.network,
+        "amount_msat": invoice.amount_msat,
+        "timestamp": invoice.timestamp,
+        "expiry": invoice.expiry,
+        "expires_at": invoice.timestamp.saturating_add(invoice.expiry),
+        "payment_hash": invoice.payment_hash,
+        "payment_secret": invoice.payment_secret,
+        "description": invoice.description,
+        "description_hash": invoice.description_hash,
+        "min_final_cltv_expiry": invoice.min_final_cltv_expiry,
+        "payee": invoice.payee
+    });
+    Ok(result.to_string())
+}
+
+// NIP-57: Zap receipts
+
+fn nip57_tag_value(event: &Event, name: &str) -> Option<String> {
+    event.tags.iter().find_map(|tag| {
+        let values = tag.as_slice();
+        if values.len() >= 2 && values[0] == name {
+            Some(values[1].clone())
+        } else {
+            None
+        }
+    })
+}
+
+// Validates a kind 9735 receipt per NIP-57 Appendix F: it must be signed by the
+// recipient's LNURL `nostrPubkey`, embed a valid kind 9734 request, and carry an
+// invoice whose amount and description hash match that request.
+#[rustler::nif]
+fn nip57_validate_zap_receipt_nif(receipt_json: String, nostr_pubkey: String) -> NifResult<String> {
+    use nostr::hashes::{sha256, Hash};
+    let error = |msg: &str| rustler::Error::Term(Box::new(msg.to_string()));
+
+    let receipt = to_rustler_error(Event::from_json(&receipt_json))?;
+    if receipt.kind != Kind::ZapReceipt {
+        return Err(error("Event is not a zap receipt (kind 9735)"));
+    }
+    to_rustler_error(receipt.verify())?;
+    let nostr_pubkey = to_rustler_error(PublicKey::parse(&nostr_pubkey))?;
+    if receipt.pubkey != nostr_pubkey {
+        return Err(error("Zap receipt is not signed by the LNURL nostrPubkey"));
+    }
+
+    let description = nip57_tag_value(&receipt, "description").ok_or_else(|| error("Missing description tag"))?;
+    let request = to_rustler_error(Event::from_json(&description))?;
+    let bolt11 = nip57_tag_value(&receipt, "bolt11").ok_or_else(|| error("Missing bolt11 tag"))?;
+    let invoice = bolt11_decode(&bolt11).map_err(|e| rustler::Error::Term(Box::new(e)))?;
+    let description_hash = hex::encode(sha256::Hash::hash(description.as_bytes()).to_byte_array());
+    if invoice.description_hash.as_deref() != Some(description_hash.as_str()) {
+        return Err(error("Invoice description hash does not match the zap request"));
+    }
+    let data = nip57_check_zap_request(&request, invoice.amount_msat).map_err(|e| rustler::Error::Term(Box::new(e)))?;
+    if data.amount.is_some() && invoice.amount_msat.is_none() {
+        return Err(error("Invoice has no amount but the zap request does"));
+    }
+
+    let recipient = data.public_key.to_hex();
+    if nip57_tag_value(&receipt, "p").as_deref() != Some(recipient.as_str()) {
+        return Err(error("Zap receipt p tag does not match the zap request"));
+    }
+    let event_id = data.event_id.map(|id| id.to_hex());
+    if event_id.is_some() && nip57_tag_value(&receipt, "e") != event_id {
+        return Err(error("Zap receipt e tag does not match the zap request"));
+    }
+
+    let result = serde_json::json!({
+        "id": receipt.id.to_hex(),
+        "sender": request.pubkey.to_hex(),
+        "recipient": recipient,
+        "amount": invoice.amount_msat,
+        "event_id": event_id,
+        "event_coordinate": nip57_tag_value(&request, "a"),
+        "comment": data.message,
+        "anonymous": request.tags.iter().any(|tag| tag.kind() == TagKind::Anon),
+        "type": nip57_zap_type_name(nip57_zap_type(&request)),
+        "preimage": nip57_tag_value(&receipt, "preimage"),
+        "bolt11": bolt11,
+        "paid_at": receipt.created_at.as_u64()
+    });
+    Ok(result.to_string())
+}
+
+// An `anon` tag marks anonymous requests; private ones also carry the encrypted
+// sender and message as its value.
+fn nip57_zap_type(request: &Event) -> nostr::nips::nip57::ZapType {
+    use nostr::nips::nip57::ZapType;
+    match request.tags.iter().find(|tag| tag.kind() == TagKind::Anon) {
+        None => ZapType::Public,
+        Some(tag) if tag.content().is_some_and(|msg| !msg.is_empty()) => ZapType::Private,
+        Some(_) => ZapType::Anonymous,
+    }
+}
+
+fn nip57_zap_type_name(zap_type: nostr::nips::nip57::ZapType) -> &'static str {
+    use nostr::nips::nip57::ZapType;
+    match zap_type {
+        ZapType::Public => "public",
+        ZapType::Private => "private",
+        ZapType::Anonymous => "anonymous",
+    }
+}
+
+// Checks an incoming kind 9734 request per NIP-57 Appendix D and returns its data.
+// `amount_msat` is the amount the LNURL server is invoicing for.
+fn nip57_check_zap_request(request: &Event, amount_msat: Option<u64>) -> Result<nostr::nips::nip57::ZapRequestData, String> {
+    use nostr::nips::nip01::Coordinate;
+    use nostr::nips::nip57::ZapRequestData;
+    if request.kind != Kind::ZapRequest {
+        return Err("Event is not a zap request (kind 9734)".to_string());
+    }
+    request.verify().map_err(|e| e.to_string())?;
+    let tags = |name: &str| -> Vec<&[String]> {
+        request.tags.iter().map(|tag| tag.as_slice()).filter(|t| t.len() >= 2 && t[0] == name).collect()
+    };
+
+    let p_tags = tags("p");
+    if p_tags.len() != 1 {
+        return Err("Zap request must have exactly one p tag".to_string());
+    }
+    let public_key = PublicKey::parse(&p_tags[0][1]).map_err(|e| e.to_string())?;
+    if tags("P").len() > 1 {
+        return Err("Zap request must have at most one P tag".to_string());
+    }
+    let e_tags = tags("e");
+    if e_tags.len() > 1 {
+        return Err("Zap request must have at most one e tag".to_string());
+    }
+    let relays = tags("relays")
+        .first()
+        .ok_or("Zap request has no relays tag")?[1..]
+        .iter()
+        .map(|r| RelayUrl::parse(r).map_err(|e| e.to_string()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut data = ZapRequestData::new(public_key, relays).message(request.content.clone());
+    if let Some(amount) = nip57_tag_value(request, "amount") {
+        let amount: u64 = amount.parse().map_err(|_| "Invalid zap request amount")?;
+        if amount_msat.is_some_and(|msat| msat != amount) {
+            return Err("Zap request amount does not match the invoice amount".to_string());
+        }
+        data = data.amount(amount);
+    }
+    if let Some(lnurl) = nip57_tag_value(request, "lnurl") {
+        data = data.lnurl(lnurl);
+    }
+    if let Some(event_id) = e_tags.first() {
+        data = data.event_id(EventId::from_hex(&event_id[1]).map_err(|e| e.to_string())?);
+    }
+    if let Some(coordinate) = nip57_tag_value(request, "a") {
+        data = data.event_coordinate(Coordinate::from_str(&coordinate).map_err(|e| e.to_string())?);
+    }
+    Ok(data)
+}
+
+#[rustler::nif]
+fn nip57_validate_zap_request_nif(request_json: String, amount_msat: Option<u64>) -> NifResult<String> {
+    let request = to_rustler_error(Event::from_json(&request_json))?;
+    let data = nip57_check_zap_request(&request, amount_msat).map_err(|e| rustler::Error::Term(Box::new(e)))?;
+    Ok(nip57_zap_request_data_json(&data).to_string())
+}
+
+// Parses a kind 9734 request into the ZapRequestData shape, plus its author and type.
+#[rustler::nif]
+fn nip57_parse_zap_request_nif(request_json: String) -> NifResult<String> {
+    let request = to_rustler_error(Event::from_json(&request_json))?;
+    let data = nip57_check_zap_request(&request, None).map_err(|e| rustler::Error::Term(Box::new(e)))?;
+    let mut result = nip57_zap_request_data_json(&data);
+    result["sender"] = serde_json::json!(request.pubkey.to_hex());
+    result["type"] = serde_json::json!(nip57_zap_type_name(nip57_zap_type(&request)));
+    Ok(result.to_string())
+}
+
+// Builds the kind 9735 receipt for a paid invoice. The description tag carries the
+// request JSON verbatim, since the invoice description hash commits to those exact bytes.
+#[rustler::nif]
+fn nip57_zap_receipt_nif(
+    request_json: String,
+    bolt11: String,
+    preimage: Option<String>,
+    paid_at: Option<u64>,
+    secret_key: String
+) -> NifResult<String> {
+    use nostr::hashes::{sha256, Hash};
+    let error = |msg: &str| rustler::Error::Term(Box::new(msg.to_string()));
+
+    let keys = to_rustler_error(Keys::parse(&secret_key))?;
+    let request = to_rustler_error(Event::from_json(&request_json))?;
+    let invoice = bolt11_decode(&bolt11).map_err(|e| rustler::Error::Term(Box::new(e)))?;
+    let amount_msat = invoice.amount_msat.ok_or_else(|| error("Zap invoice has no amount"))?;
+    nip57_check_zap_request(&request, Some(amount_msat)).map_err(|e| rustler::Error::Term(Box::new(e)))?;
+    let description_hash = hex::encode(sha256::Hash::hash(request_json.as_bytes()).to_byte_array());
+    if invoice.description_hash.as_deref() != Some(description_hash.as_str()) {
+        return Err(error("Invoice description hash does not match the zap request"));
+    }
+
+    let mut tags: Vec<Tag> = request
+        .tags
+        .iter()
+        .filter(|tag| matches!(tag.as_slice()[0].as_str(), "p" | "e" | "a"))
+        .cloned()
+        .collect();
+    tags.push(Tag::custom(TagKind::custom("P"), [request.pubkey.to_hex()]));
+    tags.push(Tag::custom(TagKind::custom("bolt11"), [bolt11]));
+    tags.push(Tag::custom(TagKind::Description, [request_json]));
+    if let Some(preimage) = preimage {
+        tags.push(Tag::custom(TagKind::custom("preimage"), [preimage]));
+    }
+    let created_at = paid_at.map(Timestamp::from).unwrap_or_else(Timestamp::now);
+    let event = to_rustler_error(
+        EventBuilder::new(Kind::ZapReceipt, "")
+            .tags(tags)
+            .custom_created_at(created_at)
+            .sign_with_keys(&keys),
+    )?;
+    Ok(event.as_json())
+}
+
+// LNURL: bech32 lnurls (LUD-01), pay responses (LUD-06) and lightning addresses (LUD-16)
+
+#[rustler::nif]
+fn lnurl_encode_nif(url: String) -> NifResult<String> {
+    use bech32::{Bech32, Hrp};
+    let hrp = Hrp::parse("lnurl").expect("valid hrp");
+    to_rustler_error(bech32::encode::<Bech32>(hrp, url.as_bytes()))
+}
+
+#[rustler::nif]
+fn lnurl_decode_nif(lnurl: String) -> NifResult<String> {
+    use bech32::primitives::decode::CheckedHrpstring;
+    use bech32::Bech32;
+    let lnurl = lnurl.trim().to_lowercase();
+    let lnurl = lnurl.strip_prefix("lightning:").unwrap_or(&lnurl);
+    let checked = to_rustler_error(CheckedHrpstring::new::<Bech32>(lnurl))?;
+    if checked.hrp().to_lowercase() != "lnurl" {
+        return Err(rustler::Error::Term(Box::new("Not an lnurl".to_string())));
+    }
+    to_rustler_error(String::from_utf8(checked.byte_iter().collect()))
+}
+
+// A DNS hostname (lowercase, as lightning addresses are normalized) with an optional port.
+fn lnurl_valid_domain(domain: &str) -> bool {
+    let (host, port) = match domain.rsplit_once(':') {
+        Some((host, port)) => (host, Some(port)),
+        None => (domain, None),
+    };
+    let valid_port = port.is_none_or(|port| port.parse::<u16>().is_ok_and(|port| port > 0));
+    let valid_label = |label: &str| {
+        !label.is_empty()
+            && label.len() <= 63
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    };
+    valid_port && !host.is_empty() && host.len() <= 253 && host.split('.').all(valid_label)
+}
+
+// Maps `name@domain` to `https://domain/.well-known/lnurlp/name` (plain http for onion
+// domains, as LUD-16 allows) and returns both the URL and its bech32 lnurl.
+#[rustler::nif]
+fn lud16_to_lnurl_nif(address: String) -> NifResult<String> {
+    use bech32::{Bech32, Hrp};
+    let error = |msg: &str| rustler::Error::Term(Box::new(msg.to_string()));
+    let address = address.trim().to_lowercase();
+    let (name, domain) = address.split_once('@').ok_or_else(|| error("Lightning address must be name@domain"))?;
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || "-_.+".contains(c)) {
+        return Err(error("Invalid lightning address name"));
+    }
+    if !lnurl_valid_domain(domain) {
+        return Err(error("Invalid lightning address domain"));
+    }
+    let scheme = if domain.ends_with(".onion") { "http" } else { "https" };
+    let url = format!("{}://{}/.well-known/lnurlp/{}", scheme, domain, name);
+    let lnurl = to_rustler_error(bech32::encode::<Bech32>(Hrp::parse("lnurl").expect("valid hrp"), url.as_bytes()))?;
+    Ok(serde_json::json!({ "url": url, "lnurl": lnurl }).to_string())
+}
+
+// Extracts the fields zaps need from a LUD-06 payRequest response body.
+#[rustler::nif]
+fn lnurl_parse_pay_response_nif(body: String) -> NifResult<String> {
+    let error = |msg: String| rustler::Error::Term(Box::new(msg));
+    let response: serde_json::Value = to_rustler_error(serde_json::from_str(&body))?;
+    if response["status"].as_str().is_some_and(|status| status.eq_ignore_ascii_case("ERROR")) {
+        let reason = response["reason"].as_str().unwrap_or("unknown error");
+        return Err(error(format!("LNURL service error: {}", reason)));
+    }
+    if response["tag"].as_str() != Some("payRequest") {
+        return Err(error("Not an LNURL-pay response".to_string()));
+    }
+    let callback = response["callback"].as_str().ok_or_else(|| error("Missing callback".to_string()))?;
+    let min_sendable = response["minSendable"].as_u64().ok_or_else(|| error("Missing minSendable".to_string()))?;
+    let max_sendable = response["maxSendable"].as_u64().ok_or_else(|| error("Missing maxSendable".to_string()))?;
+    let allows_nostr = response["allowsNostr"].as_bool().unwrap_or(false);
+    let nostr_pubkey = match response["nostrPubkey"].as_str() {
+        Some(pubkey) if allows_nostr => Some(to_rustler_error(PublicKey::parse(pubkey))?.to_hex()),
+        _ => None,
+    };
+    if allows_nostr && nostr_pubkey.is_none() {
+        return Err(error("allowsNostr is set but nostrPubkey is missing".to_string()));
+    }
+    let result = serde_json::json!({
+        "callback": callback,
+        "min_sendable": min_sendable,
+        "max_sendable": max_sendable,
+        "metadata": response["metadata"].as_str(),
+        "comment_allowed": response["commentAllowed"].as_u64().unwrap_or(0),
+        "allows_nostr": allows_nostr,
+        "nostr_pubkey": nostr_pubkey
+    });
+    Ok(result.to_string())
+}
+
+// NIP-57: Zap splits (Appendix G)
+
+struct ZapSplit {
+    public_key: PublicKey,
+    relay: Option<String>,
+    weight: Option<u64>,
+}
+
+fn nip57_zap_splits(event: &Event) -> Result<Vec<ZapSplit>, String> {
+    event
+        .tags
+        .iter()
+        .map(|tag| tag.as_slice())
+        .filter(|values| values.len() >= 2 && values[0] == "zap")
+        .map(|values| {
+            let weight = match values.get(3) {
+                Some(weight) => Some(weight.parse::<u64>().map_err(|_| format!("Invalid zap weight: {}", weight))?),
+                None => None,
+            };
+            Ok(ZapSplit {
+                public_key: PublicKey::parse(&values[1]).map_err(|e| e.to_string())?,
+                relay: values.get(2).filter(|relay| !relay.is_empty()).cloned(),
+                weight,
+            })
+        })
+        .collect()
+}
+
+// Splits `amount_msat` by weight. Recipients without a weight get nothing when others
+// have one, and an equal share when nobody does. Rounding uses the largest remainder
+// method so the shares always add up to the total.
+fn nip57_split_amount(splits: &[ZapSplit], amount_msat: u64) -> Result<Vec<u64>, String> {
+    let weighted = splits.iter().any(|split| split.weight.is_some());
+    let weights: Vec<u128> = splits
+        .iter()
+        .map(|split| if weighted { split.weight.unwrap_or(0) as u128 } else { 1 })
+        .collect();
+    let total_weight: u128 = weights.iter().sum();
+    if total_weight == 0 {
+        return Err("Zap splits have no positive weight".to_string());
+    }
+    let total = amount_msat as u128;
+    let mut amounts: Vec<u64> = weights.iter().map(|weight| (total * weight / total_weight) as u64).collect();
+    let mut remainder = amount_msat - amounts.iter().sum::<u64>();
+    let mut order: Vec<usize> = (0..splits.len()).collect();
+    order.sort_by_key(|&i| std::cmp::Reverse(total * weights[i] % total_weight));
+    for i in order {
+        if remainder == 0 {
+            break;
+        }
+        if weights[i] > 0 {
+            amounts[i] += 1;
+            remainder -= 1;
+        }
+    }
+    Ok(amounts)
+}
+
+#[rustler::nif]
+fn nip57_zap_split_tags_nif(splits: Vec<(String, Option<String>, Option<u64>)>) -> NifResult<Vec<Vec<String>>> {
+    splits
+        .into_iter()
+        .map(|(public_key, relay, weight)| {
+            let public_key = to_rustler_error(PublicKey::parse(&public_key))?;
+            let mut tag = vec!["zap".to_string(), public_key.to_hex(), relay.unwrap_or_default()];
+            if let Some(weight) = weight {
+                tag.push(weight.to_string());
+            }
+            Ok(tag)
+        })
+        .collect()
+}
+
+#[rustler::nif]
+fn nip57_parse_zap_splits_nif(event_json: String) -> NifResult<String> {
+    let event = to_rustler_error(Event::from_json(&event_json))?;
+    let splits = nip57_zap_splits(&event).map_err(|e| rustler::Error::Term(Box::new(e)))?;
+    let result: Vec<serde_json::Value> = splits
+        .iter()
+        .map(|split| serde_json::json!({ "public_key": split.public_key.to_hex(), "relay": split.relay, "weight": split.weight }))
+        .collect();
+    Ok(serde_json::Value::Array(result).to_string())
+}
+
+#[rustler::nif]
+fn nip57_split_amount_nif(event_json: String, amount_msat: u64) -> NifResult<String> {
+    let event = to_rustler_error(Event::from_json(&event_json))?;
+    let splits = nip57_zap_splits(&event).map_err(|e| rustler::Error::Term(Box::new(e)))?;
+    let amounts = nip57_split_amount(&splits, amount_msat).map_err(|e| rustler::Error::Term(Box::new(e)))?;
+    let result: Vec<serde_json::Value> = splits
+        .iter()
+        .zip(amounts)
+        .map(|(split, amount)| serde_json::json!({ "public_key": split.public_key.to_hex(), "relay": split.relay, "amount": amount }))
+        .collect();
+    Ok(serde_json::Value::Array(result).to_string())
+}
+
+// One zap request per split recipient that receives a non-zero share, targeting the
+// event (and its coordinate when addressable). Signed with `secret_key` when given,
+// anonymous otherwise.
+#[rustler::nif]
+fn nip57_split_zap_requests_nif(
+    event_json: String,
+    amount_msat: u64,
+    relays: Vec<String>,
+    message: String,
+    secret_key: Option<String>
+) -> NifResult<String> {
+    use nostr::nips::nip57::anonymous_zap_request;
+    let event = to_rustler_error(Event::from_json(&event_json))?;
+    let splits = nip57_zap_splits(&event).map_err(|e| rustler::Error::Term(Box::new(e)))?;
+    if splits.is_empty() {
+        return Err(rustler::Error::Term(Box::new("Event has no zap tags".to_string())));
+    }
+    let amounts = nip57_split_amount(&splits, amount_msat).map_err(|e| rustler::Error::Term(Box::new(e)))?;
+    let keys = secret_key.map(|sk| to_rustler_error(Keys::parse(&sk))).transpose()?;
+    let coordinate = event.coordinate().filter(|_| event.kind.is_addressable()).map(|c| c.into_owned().to_string());
+
+    let mut result = Vec::new();
+    for (split, amount) in splits.iter().zip(amounts) {
+        if amount == 0 {
+            continue;
+        }
+        let mut request_relays = relays.clone();
+        if let Some(relay) = split.relay.as_ref().filter(|relay| !relays.contains(relay)) {
+            request_relays.push(relay.clone());
+        }
+        let data = nip57_zap_request_data(
+            split.public_key.to_hex(),
+            request_relays,
+            message.clone(),
+            Some(amount),
+            None,
+            Some(event.id.to_hex()),
+            coordinate.clone(),
+        )?;
+        let request = match &keys {
+            Some(keys) => to_rustler_error(EventBuilder::public_zap_request(data).sign_with_keys(keys))?,
+            None => to_rustler_error(anonymous_zap_request(data))?,
+        };
+        result.push(serde_json::json!({
+            "public_key": split.public_key.to_hex(),
+            "amount": amount,
+            "zap_request": request.as_json()
+        }));
+    }
+    Ok(serde_json::Value::Array(result).to_string())
+}
+
+// NIP-47: Nostr Wallet Connect
+//
+// nostr's NIP-47 types only speak NIP-04, so payloads are encrypted here with whichever
+// scheme the wallet's kind 13194 info event advertises.
+
+const NIP47_INFO_KIND: u16 = 13194;
+const NIP47_REQUEST_KIND: u16 = 23194;
+const NIP47_RESPONSE_KIND: u16 = 23195;
+const NIP47_NOTIFICATION_KIND: u16 = 23196;
+const NIP47_NOTIFICATION_NIP44_KIND: u16 = 23197;
+
+fn nip47_encrypt(encryption: &str, secret_key: &SecretKey, public_key: &PublicKey, plaintext: &str) -> NifResult<String> {
+    use nostr::nips::nip04;
+    use nostr::nips::nip44::v2::ConversationKey;
+    match encryption {
+        "nip44_v2" => nip44_encrypt_with(&to_rustler_error(ConversationKey::derive(secret_key, public_key))?, plaintext),
+        "nip04" => to_rustler_error(nip04::encrypt(secret_key, public_key, plaintext)),
+        other => Err(rustler::Error::Term(Box::new(format!("Unsupported NWC encryption: {}", other)))),
+    }
+}
+
+fn nip47_decrypt_with(encryption: &str, secret_key: &SecretKey, public_key: &PublicKey, payload: &str) -> NifResult<String> {
+    use nostr::nips::nip04;
+    use nostr::nips::nip44::v2::ConversationKey;
+    match encryption {
+        "nip44_v2" => nip44_decrypt_with(&to_rustler_error(ConversationKey::derive(secret_key, public_key))?, payload),
+        "nip04" => to_rustler_error(nip04::decrypt(secret_key, public_key, payload)),
+        other => Err(rustler::Error::Term(Box::new(format!("Unsupported NWC encryption: {}", other)))),
+    }
+}
+
+// NIP-04 ciphertexts carry an `?iv=` suffix; NIP-44 payloads are plain base64.
+fn nip47_payload_encryption(payload: &str) -> &'static str {
+    if payload.contains("?iv=") { "nip04" } else { "nip44_v2" }
+}
+
+// The reply scheme is implied by the payload.
+fn nip47_decrypt(secret_key: &SecretKey, public_key: &PublicKey, payload: &str) -> NifResult<String> {
+    nip47_decrypt_with(nip47_payload_encryption(payload), secret_key, public_key, payload)
+}
+
+fn nip47_uri(uri: &str) -> NifResult<nostr::nips::nip47::NostrWalletConnectURI> {
+    to_rustler_error(nostr::nips::nip47::NostrWalletConnectURI::parse(uri))
+}
+
+// Checks kind, signature and author of an event sent by the wallet service.
+fn nip47_wallet_event(uri: &nostr::nips::nip47::NostrWalletConnectURI, event_json: &str, kinds: &[u16]) -> NifResult<Event> {
+    let event = to_rustler_error(Event::from_json(event_json))?;
+    if !kinds.contains(&event.kind.as_u16()) {
+        return Err(rustler::Error::Term(Box::new(format!("Unexpected event kind {}", event.kind.as_u16()))));
+    }
+    to_rustler_error(event.verify())?;
+    if event.pubkey != uri.public_key {
+        return Err(rustler::Error::Term(Box::new("Event is not from the connected wallet".to_string())));
+    }
+    Ok(event)
+}
+
+#[rustler::nif]
+fn nip47_parse_uri_nif(uri: String) -> NifResult<String> {
+    let uri = nip47_uri(&uri)?;
+    let client = Keys::new(uri.secret.clone());
+    let result = serde_json::json!({
+        "wallet_pubkey": uri.public_key.to_hex(),
+        "relays": uri.relays.iter().map(|r| r.to_string()).collect::<Vec<_>>(),
+        "secret": uri.secret.to_secret_hex(),
+        "client_pubkey": client.public_key().to_hex(),
+        "lud16": uri.lud16
+    });
+    Ok(result.to_string())
+}
+
+// Without an `encryption` tag a wallet only supports NIP-04.
+#[rustler::nif]
+fn nip47_parse_info_nif(uri: String, info_json: String) -> NifResult<String> {
+    let uri = nip47_uri(&uri)?;
+    let info = nip47_wallet_event(&uri, &info_json, &[NIP47_INFO_KIND])?;
+    let list = |name: &str| -> Option<Vec<String>> {
+        nip57_tag_value(&info, name).map(|value| value.split_whitespace().map(String::from).collect())
+    };
+    let result = serde_json::json!({
+        "wallet_pubkey": info.pubkey.to_hex(),
+        "methods": info.content.split_whitespace().collect::<Vec<_>>(),
+        "notifications": list("notifications").unwrap_or_default(),
+        "encryption": list("encryption").unwrap_or_else(|| vec!["nip04".to_string()])
+    });
+    Ok(result.to_string())
+}
+
+// Builds a kind 23194 request. `params_json` is validated against the method's NIP-47
+// params, then encrypted to the wallet as given with `encryption`, so omitted optional
+// params are not sent as nulls.
+#[rustler::nif]
+fn nip47_request_nif(
+    uri: String,
+    method: String,
+    params_json: String,
+    encryption: String,
+    expiration: Option<u64>
+) -> NifResult<String> {
+    use nostr::nips::nip47::Request;
+    let uri = nip47_uri(&uri)?;
+    let params: serde_json::Value = to_rustler_error(serde_json::from_str(&params_json))?;
+    let request = serde_json::json!({ "method": method, "params": params });
+    to_rustler_error(Request::from_value(request.clone()))?;
+    let content = nip47_encrypt(&encryption, &uri.secret, &uri.public_key, &request.to_string())?;
+
+    let mut tags = vec![Tag::public_key(uri.public_key)];
+    if encryption != "nip04" {
+        tags.push(Tag::custom(TagKind::custom("encryption"), [encryption]));
+    }
+    if let Some(expiration) = expiration {
+        tags.push(Tag::expiration(Timestamp::from(expiration)));
+    }
+    let keys = Keys::new(uri.secret.clone());
+    let event = to_rustler_error(
+        EventBuilder::new(Kind::from(NIP47_REQUEST_KIND), content)
+            .tags(tags)
+            .sign_with_keys(&keys),
+    )?;
+    Ok(event.as_json())
+}
+
+#[rustler::nif]
+fn nip47_parse_response_nif(uri: String, event_json: String) -> NifResult<String> {
+    use nostr::nips::nip47::Response;
+    let uri = nip47_uri(&uri)?;
+    let event = nip47_wallet_event(&uri, &event_json, &[NIP47_RESPONSE_KIND])?;
+    let plaintext = nip47_decrypt(&uri.secret, &event.pubkey, &event.content)?;
+    let response: serde_json::Value = to_rustler_error(serde_json::from_str(&plaintext))?;
+    // Error replies are passed through as is, so codes and methods nostr does not know
+    // about (e.g. NOT_IMPLEMENTED for a newer method) still reach the caller.
+    let mut result = if response["error"].is_object() {
+        serde_json::json!({
+            "result_type": response["result_type"],
+            "error": response["error"],
+            "result": null
+        })
+    } else {
+        to_rustler_error(serde_json::to_value(to_rustler_error(Response::from_value(response))?))?
+    };
+    result["request_id"] = serde_json::json!(nip57_tag_value(&event, "e"));
+    Ok(result.to_string())
+}
+
+#[rustler::nif]
+fn nip47_parse_notification_nif(uri: String, event_json: String) -> NifResult<String> {
+    use nostr::nips::nip47::Notification;
+    let uri = nip47_uri(&uri)?;
+    let event = nip47_wallet_event(&uri, &event_json, &[NIP47_NOTIFICATION_KIND, NIP47_NOTIFICATION_NIP44_KIND])?;
+    let plaintext = nip47_decrypt(&uri.secret, &event.pubkey, &event.content)?;
+    let notification = to_rustler_error(Notification::from_json(&plaintext))?;
+    to_rustler_error(serde_json::to_string(&notification))
+}
+
+// NIP-47: wallet service side
+
+const NIP47_ERROR_CODES: &[&str] = &[
+    "RATE_LIMITED",
+    "NOT_IMPLEMENTED",
+    "INSUFFICIENT_BALANCE",
+    "PAYMENT_FAILED",
+    "NOT_FOUND",
+    "QUOTA_EXCEEDED",
+    "RESTRICTED",
+    "UNAUTHORIZED",
+    "UNSUPPORTED_ENCRYPTION",
+    "INTERNAL",
+    "OTHER",
+];
+
+// Checks that a kind 23194 request is addressed to `wallet`, without decrypting it.
+fn nip47_check_request(wallet: &Keys, event: &Event) -> NifResult<()> {
+    let error = |msg: &str| rustler::Error::Term(Box::new(msg.to_string()));
+    if event.kind.as_u16() != NIP47_REQUEST_KIND {
+        return Err(error("Event is not a wallet connect request (kind 23194)"));
+    }
+    to_rustler_error(event.verify())?;
+    if nip57_tag_value(event, "p") != Some(wallet.public_key().to_hex()) {
+        return Err(error("Request is not addressed to this wallet"));
+    }
+    if event.is_expired() {
+        return Err(error("Request has expired"));
+    }
+    Ok(())
+}
+
+// Requests carry an `encryption` tag when they use NIP-44; its absence means NIP-04.
+// The payload must actually use the tagged scheme, otherwise the reply (which uses the
+// tagged scheme) would be unreadable for the client.
+fn nip47_request_encryption(event: &Event) -> Result<&'static str, String> {
+    let encryption = match nip57_tag_value(event, "encryption").as_deref() {
+        None | Some("nip04") => "nip04",
+        Some("nip44_v2") => "nip44_v2",
+        Some(other) => return Err(format!("Unsupported encryption: {}", other)),
+    };
+    if nip47_payload_encryption(&event.content) != encryption {
+        return Err(format!("Unsupported encryption: payload is not {}", encryption));
+    }
+    Ok(encryption)
+}
+
+// Decrypts a checked request into its raw `{method, params}` body. Methods are kept as
+// strings so requests for methods nostr does not know about can still be answered.
+fn nip47_decrypt_request(wallet: &Keys, event: &Event, encryption: &str) -> NifResult<serde_json::Value> {
+    let plaintext = nip47_decrypt_with(encryption, wallet.secret_key(), &event.pubkey, &event.content)?;
+    let request: serde_json::Value = to_rustler_error(serde_json::from_str(&plaintext))?;
+    if !request["method"].is_string() {
+        return Err(rustler::Error::Term(Box::new("Request has no method".to_string())));
+    }
+    Ok(request)
+}
+
+#[rustler::nif]
+fn nip47_info_event_nif(
+    secret_key: String,
+    methods: Vec<String>,
+    encryption: Vec<String>,
+    notifications: Vec<String>
+) -> NifResult<String> {
+    let keys = to_rustler_error(Keys::parse(&secret_key))?;
+    let mut tags = Vec::new();
+    if !encryption.is_empty() {
+        tags.push(Tag::custom(TagKind::custom("encryption"), [encryption.join(" ")]));
+    }
+    if !notifications.is_empty() {
+        tags.push(Tag::custom(TagKind::custom("notifications"), [notifications.join(" ")]));
+    }
+    let event = to_rustler_error(
+        EventBuilder::new(Kind::from(NIP47_INFO_KIND), methods.join(" "))
+            .tags(tags)
+            .sign_with_keys(&keys),
+    )?;
+    Ok(event.as_json())
+}
+
+// `authorized` holds the client public keys of the connection secrets this wallet issued.
+#[rustler::nif]
+fn nip47_parse_request_nif(secret_key: String, event_json: String, authorized: Vec<String>) -> NifResult<String> {
+    let wallet = to_rustler_error(Keys::parse(&secret_key))?;
+    let event = to_rustler_error(Event::from_json(&event_json))?;
+    let authorized = authorized
+        .iter()
+        .map(|pubkey| to_rustler_error(PublicKey::parse(pubkey)))
+        .collect::<NifResult<Vec<_>>>()?;
+    if !authorized.contains(&event.pubkey) {
+        return Err(rustler::Error::Term(Box::new("Connection is not authorized".to_string())));
+    }
+    nip47_check_request(&wallet, &event)?;
+    let encryption = nip47_request_encryption(&event).map_err(|e| rustler::Error::Term(Box::new(e)))?;
+    let request = nip47_decrypt_request(&wallet, &event, encryption)?;
+    let result = serde_json::json!({
+        "id": event.id.to_hex(),
+        "client_pubkey": event.pubkey.to_hex(),
+        "method": request["method"],
+        "params": request["params"],
+        "encryption": encryption,
+        "expiration": event.tags.expiration().map(|t| t.as_u64())
+    });
+    Ok(result.to_string())
+}
+
+// Builds the kind 23195 reply to `request_json`, encrypted with the request's scheme.
+// `response_json` holds either `result` or `error` ({code, message}); its `result_type`
+// is taken from the request method. Results are only accepted for methods nostr knows,
+// errors for any method. Requests with an unsupported encryption can only get an error,
+// sent with NIP-04 and without a `result_type`.
+#[rustler::nif]
+fn nip47_response_nif(secret_key: String, request_json: String, response_json: String) -> NifResult<String> {
+    use nostr::nips::nip47::Response;
+    let error = |msg: String| rustler::Error::Term(Box::new(msg));
+    let wallet = to_rustler_error(Keys::parse(&secret_key))?;
+    let event = to_rustler_error(Event::from_json(&request_json))?;
+    nip47_check_request(&wallet, &event)?;
+    let response: serde_json::Value = to_rustler_error(serde_json::from_str(&response_json))?;
+    let is_error = !response["error"].is_null();
+    if is_error {
+        let code = response["error"]["code"].as_str().unwrap_or_default();
+        if !NIP47_ERROR_CODES.contains(&code) {
+            return Err(error(format!("Unknown NWC error code: {}", code)));
+        }
+    } else if response["result"].is_null() {
+        return Err(error("Response needs a result or an error".to_string()));
+    }
+
+    let (encryption, method) = match nip47_request_encryption(&event) {
+        Ok(encryption) => (encryption, Some(nip47_decrypt_request(&wallet, &event, encryption)?["method"].clone())),
+        Err(_) if is_error => ("nip04", None),
+        Err(e) => return Err(error(e)),
+    };
+    let body = if is_error {
+        serde_json::json!({ "result_type": method, "error": response["error"], "result": null })
+    } else {
+        let body = serde_json::json!({ "result_type": method, "error": null, "result": response["result"] });
+        to_rustler_error(Response::from_value(body.clone()))?;
+        body
+    };
+    let content = nip47_encrypt(encryption, wallet.secret_key(), &event.pubkey, &body.to_string())?;
+    let reply = to_rustler_error(
+        EventBuilder::new(Kind::from(NIP47_RESPONSE_KIND), content)
+            .tags([Tag::public_key(event.pubkey), Tag::event(event.id)])
+            .sign_with_keys(&wallet),
+    )?;
+    Ok(reply.as_json())
+}
+
+// NIP-04 notifications use kind 23196 and NIP-44 ones kind 23197.
+#[rustler::nif]
+fn nip47_notification_nif(
+    secret_key: String,
+    client_pubkey: String,
+    notification_json: String,
+    encryption: String
+) -> NifResult<String> {
+    use nostr::nips::nip47::Notification;
+    let wallet = to_rustler_error(Keys::parse(&secret_key))?;
+    let client = to_rustler_error(PublicKey::parse(&client_pubkey))?;
+    let notification = to_rustler_error(Notification::from_json(&notification_json))?;
+    let content = nip47_encrypt(&encryption, wallet.secret_key(), &client, &notification.as_json())?;
+    let kind = if encryption == "nip04" { NIP47_NOTIFICATION_KIND } else { NIP47_NOTIFICATION_NIP44_KIND };
+    let event = to_rustler_error(
+        EventBuilder::new(Kind::from(kind), content)
+            .tag(Tag::public_key(client))
+            .sign_with_keys(&wallet),
+    )?;
+    Ok(event.as_json())
+}
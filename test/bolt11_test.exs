@@ -0,0 +1,69 @@
+defmodule NostrElixir.Bolt11Test do
+  use ExUnit.Case, async: true
+  alias NostrElixir.Bolt11
+
+  @invoice "lnbc10u1p3unwfusp5t9r3yymhpfqculx78u027lxspgxcr2n2987mx2j55nnfs95nxnzqpp5jmrh92pfld78spqs78v9euf2385t83uvpwk9ldrlvf6ch7tpascqhp5zvkrmemgth3tufcvflmzjzfvjt023nazlhljz2n9hattj4f8jq8qxqyjw5qcqpjrzjqtc4fc44feggv7065fqe5m4ytjarg3repr5j9el35xhmtfexc42yczarjuqqfzqqqqqqqqlgqqqqqqgq9q9qxpqysgq079nkq507a5tw7xgttmj4u990j7wfggtrasah5gd4ywfr2pjcn29383tphp4t48gquelz9z78p4cq7ml3nrrphw5w6eckhjwmhezhnqpy6gyf0"
+
+  # Testnet invoices with an explicit `n` payee tag; the second is signed by another key.
+  @signed "lntb1m1pj48ugqpp5qyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqsdq2vdhkven9v5np4qd3vpgzxmtxwsmwaqdpud578c7wzyz96pkwfeuj2d5zx6gwjr7g0wujxfn05n3kdgqunk7l034yn02d7apkjpnrz43pnwvg0ny3qsjdgpd0mtlv6nhxmmce9m4345tarjj67u6x9qur7mkvmrf0wq5kw2wysq5uynas"
+  @forged "lntb1m1pj48ugqpp5qyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqsdq2vdhkven9v5np4qd3vpgzxmtxwsmwaqdpud578c7wzyz96pkwfeuj2d5zx6gwjr7g0wj8h0x8u70ym57xtszt4wlsedd7fmk03a62a4vrg5l9n9f0quqw8578qvj0zqkudxxz0gcevzymljd2scpfztrsrueulzj66du678rdcpdaeaqc"
+
+  # Signed invoice with a 600-byte description and a five-hop route hint, longer than
+  # the 1023 characters plain bech32 allows.
+  @long_invoice "lnbc1pj48ugqpp5qyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqsd7qwfhh2ar9yp5xjmn5wvs8ymm4w3jjq6rfde68xgrjda6hgefqdp5kuarnypex7at5v5sxs6tww3ejqun0w46x2grgd9h8gueqwfhh2ar9yp5xjmn5wvs8ymm4w3jjq6rfde68xgrjda6hgefqdp5kuarnypex7at5v5sxs6tww3ejqun0w46x2grgd9h8gueqwfhh2ar9yp5xjmn5wvs8ymm4w3jjq6rfde68xgrjda6hgefqdp5kuarnypex7at5v5sxs6tww3ejqun0w46x2grgd9h8gueqwfhh2ar9yp5xjmn5wvs8ymm4w3jjq6rfde68xgrjda6hgefqdp5kuarnypex7at5v5sxs6tww3ejqun0w46x2grgd9h8gueqwfhh2ar9yp5xjmn5wvs8ymm4w3jjq6rfde68xgrjda6hgefqdp5kuarnypex7at5v5sxs6tww3ejqun0w46x2grgd9h8gueqwfhh2ar9yp5xjmn5wvs8ymm4w3jjq6rfde68xgrjda6hgefqdp5kuarnypex7at5v5sxs6tww3ejqun0w46x2grgd9h8gueqwfhh2ar9yp5xjmn5wvs8ymm4w3jjq6rfde68xgrjda6hgefqdp5kuarnypex7at5v5sxs6tww3ejqun0w46x2grgd9h8gueqwfhh2ar9yp5xjmn5wvs8ymm4w3jjq6rfde68xgrjda6hgefqdp5kuarnypex7at5v5sxs6tww3ejqun0w46x2grgd9h8gueqwfhh2ar9yp5xjmn5wvs8ymm4w3jjq6rfde68xgrjda6hgefqdp5kuarnypex7at5v5sxs6tww3ejqun0w46x2grgd9h8gueqwfhh2ar9yp5xjmn5wvs8ymm4w3jjq6rfde68xgrjda6hgefqdp5kuarnypex7at5v5sxs6tww3ejqun0w46x2grgd9h8gueqrvcqgg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zqqqqqqqqqqqqqqqqqlgqqqqqeqq9qppyysjzgfpyysjzgfpyysjzgfpyysjzgfpyysjzgfpyysjzgfpyyspqyqszqgpqyqsqqqraqqqqqryqq5qyycnzvf3xycnzvf3xycnzvf3xycnzvf3xycnzvf3xycnzvf3xycnqgpqyqszqgpqyqqqq05qqqqqvsqzsqs5zs2pg9q5zs2pg9q5zs2pg9q5zs2pg9q5zs2pg9q5zs2pg9q5zspsxqcrqvpsxqcqqqp7sqqqqpjqq2qzz52329g4z52329g4z52329g4z52329g4z52329g4z52329g4z52sgpqyqszqgpqyqqqq86qqqqqxgqpgqfllsf2nnl59v74z73xkgvz9ldc8687tc8fp3lqsd46pexmda0trc7zkt3pp5gmta489hnpkdffsgq8ysf93l7kn0mwu5r9ky6taunqqn79aqa"
+
+  describe "decode/1" do
+    test "decodes a mainnet invoice and recovers the payee" do
+      invoice = Bolt11.decode(@invoice)
+      assert invoice.network == :bitcoin
+      assert invoice.amount_msat == 1_000_000
+      assert invoice.timestamp == 1_674_164_540
+      assert invoice.expiry == 604_800
+      assert invoice.expires_at == 1_674_769_340
+      assert invoice.payment_hash == "96c772a829fb7c780410f1d85cf12a89e8b3c78c0bac5fb47f62758bf961ec30"
+      assert invoice.description_hash == "132c3de7685de2be270c4ff629092c92dea8cfa2fdff212a65bf56b95527900e"
+      assert invoice.description == nil
+      assert invoice.payee == "034ab72222732a17be9074f66c630107516540dddd1e470dc814f80771a580c66d"
+    end
+
+    test "accepts a lightning: prefix and upper case" do
+      assert Bolt11.decode("lightning:" <> String.upcase(@invoice)).amount_msat == 1_000_000
+    end
+
+    test "verifies the signature against the n tag" do
+      invoice = Bolt11.decode(@signed)
+      assert invoice.network == :testnet
+      assert invoice.amount_msat == 100_000_000
+      assert invoice.description == "coffee"
+      assert invoice.expiry == 3600
+      assert invoice.payee == "0362c0a046dacce86ddd0343c6d3c7c79c2208ba0d9c9cf24a6d046d21d21f90f7"
+
+      assert_raise ArgumentError, ~r/BOLT11 decode failed: Invalid invoice signature/, fn ->
+        Bolt11.decode(@forged)
+      end
+    end
+
+    test "decodes invoices longer than 1023 characters" do
+      assert String.length(@long_invoice) > 1023
+      invoice = Bolt11.decode(@long_invoice)
+      assert invoice.timestamp == 1_700_000_000
+      assert invoice.description == String.duplicate("route hints ", 50)
+      assert invoice.payee == "0256b328b30c8bf5839e24058747879408bdb36241dc9c2e7c619faa12b2920967"
+    end
+
+    test "rejects malformed invoices" do
+      assert_raise ArgumentError, ~r/BOLT11 decode failed:/, fn ->
+        Bolt11.decode(String.replace_suffix(@invoice, "gyf0", "gyf1"))
+      end
+    end
+  end
+
+  describe "expired?/2" do
+    test "compares the expiry against the given time" do
+      invoice = Bolt11.decode(@invoice)
+      refute Bolt11.expired?(invoice, 1_674_769_339)
+      assert Bolt11.expired?(invoice, 1_674_769_340)
+      assert Bolt11.expired?(invoice)
+    end
+  end
+end
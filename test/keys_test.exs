@@ -100,4 +100,11 @@ defmodule NostrElixir.KeysTest do
     assert String.starts_with?(keys.npub, "npub")
     assert String.starts_with?(keys.nsec, "nsec")
   end
+
+  test "secret_key accepts a keys map, keys JSON or a raw secret key" do
+    keys = Keys.generate_keypair()
+    assert Keys.secret_key(keys) == keys.secret_key
+    assert Keys.secret_key(Keys.parse_keys(keys.secret_key)) == keys.secret_key
+    assert Keys.secret_key(keys.nsec) == keys.nsec
+  end
 end
@@ -0,0 +1,81 @@
+defmodule NostrElixir.LnurlTest do
+  use ExUnit.Case, async: true
+  alias NostrElixir.Lnurl
+  alias NostrElixir.Lnurl.PayResponse
+
+  # LUD-01 example
+  @lnurl "LNURL1DP68GURN8GHJ7UM9WFMXJCM99E3K7MF0V9CXJ0M385EKVCENXC6R2C35XVUKXEFCV5MKVV34X5EKZD3EV56NYD3HXQURZEPEXEJXXEPNXSCRVWFNV9NXZCN9XQ6XYEFHVGCXXCMYXYMNSERXFQ5FNS"
+  @url "https://service.com/api?q=3fc3645b439ce8e7f2553a69e5267081d96dcd340693afabe04be7b0ccd178df"
+
+  @pay_response ~S({"callback":"https://example.com/lnurlp/alice/callback","maxSendable":100000000000,"minSendable":1000,"metadata":"[[\"text/plain\",\"Pay alice\"]]","tag":"payRequest","commentAllowed":255,"allowsNostr":true,"nostrPubkey":"fa3b4f81a620c66514bda0302847df167ed02a483141b5939e57bdd0cf76ad3b"})
+
+  describe "encode/1 and decode/1" do
+    test "match the LUD-01 example" do
+      assert Lnurl.decode(@lnurl) == @url
+      assert Lnurl.decode("lightning:" <> @lnurl) == @url
+      assert Lnurl.encode(@url) == String.downcase(@lnurl)
+    end
+
+    test "reject other bech32 strings" do
+      assert_raise ArgumentError, ~r/LNURL decode failed:/, fn ->
+        Lnurl.decode("npub180cvv07tjdrrgpa0j7j7tmnyl2yr6yr7l8j4s3evf6u64th6gkwsyjh6w6")
+      end
+    end
+  end
+
+  describe "from_lightning_address/1" do
+    test "builds the well-known LNURL-pay URL" do
+      %{url: url, lnurl: lnurl} = Lnurl.from_lightning_address("Alice@Example.com")
+      assert url == "https://example.com/.well-known/lnurlp/alice"
+      assert Lnurl.decode(lnurl) == url
+    end
+
+    test "uses http for onion domains" do
+      assert Lnurl.from_lightning_address("bob@abc.onion").url == "http://abc.onion/.well-known/lnurlp/bob"
+    end
+
+    test "keeps a port in the domain" do
+      assert Lnurl.from_lightning_address("bob@localhost:8080").url == "https://localhost:8080/.well-known/lnurlp/bob"
+    end
+
+    test "rejects malformed addresses" do
+      assert_raise ArgumentError, ~r/LNURL from_lightning_address failed:/, fn ->
+        Lnurl.from_lightning_address("alice")
+      end
+    end
+
+    test "rejects domains that are not hostnames" do
+      for address <- ["alice@exa mple.com", "alice@example..com", "alice@-example.com", "alice@example.com:99999", "alice@example.com/x"] do
+        assert_raise ArgumentError, ~r/LNURL from_lightning_address failed: Invalid lightning address domain/, fn ->
+          Lnurl.from_lightning_address(address)
+        end
+      end
+    end
+  end
+
+  describe "parse_pay_response/1" do
+    test "extracts the zap fields" do
+      response = Lnurl.parse_pay_response(@pay_response)
+      assert %PayResponse{} = response
+      assert response.callback == "https://example.com/lnurlp/alice/callback"
+      assert response.min_sendable == 1000
+      assert response.max_sendable == 100_000_000_000
+      assert response.comment_allowed == 255
+      assert response.allows_nostr == true
+      assert response.nostr_pubkey == "fa3b4f81a620c66514bda0302847df167ed02a483141b5939e57bdd0cf76ad3b"
+    end
+
+    test "ignores nostrPubkey when zaps are not allowed" do
+      body = ~S({"callback":"https://example.com/cb","maxSendable":2000,"minSendable":1000,"metadata":"[]","tag":"payRequest","nostrPubkey":"fa3b4f81a620c66514bda0302847df167ed02a483141b5939e57bdd0cf76ad3b"})
+      response = Lnurl.parse_pay_response(body)
+      assert response.allows_nostr == false
+      assert response.nostr_pubkey == nil
+    end
+
+    test "raises on service errors" do
+      assert_raise ArgumentError, ~r/LNURL service error: no such user/, fn ->
+        Lnurl.parse_pay_response(~S({"status":"ERROR","reason":"no such user"}))
+      end
+    end
+  end
+end
@@ -0,0 +1,87 @@
+defmodule NostrElixir.MetadataTest do
+  use ExUnit.Case, async: true
+  alias NostrElixir.{Keys, Metadata, Event}
+
+  test "create_event builds a valid kind 0 event" do
+    keys = Keys.generate_keypair()
+    profile = %{name: "alice", display_name: "Alice", about: "Hi", picture: "https://example.com/a.png", lud16: "alice@example.com"}
+
+    event_json = Metadata.create_event(keys, profile)
+
+    assert Event.verify(event_json)
+    event = Jason.decode!(event_json)
+    assert event["kind"] == 0
+    content = Jason.decode!(event["content"])
+    assert content["name"] == "alice"
+    assert content["lud16"] == "alice@example.com"
+  end
+
+  test "parse returns all NIP-01/NIP-24 fields and custom fields" do
+    keys = Keys.generate_keypair()
+
+    profile = %Metadata{
+      name: "bot",
+      website: "https://example.com",
+      nip05: "bot@example.com",
+      bot: true,
+      birthday: %{"year" => 2020, "month" => 1, "day" => 2},
+      custom: %{"pronouns" => "it/its"}
+    }
+
+    parsed = keys |> Metadata.create_event(profile) |> Metadata.parse()
+
+    assert parsed.name == "bot"
+    assert parsed.website == "https://example.com"
+    assert parsed.nip05 == "bot@example.com"
+    assert parsed.bot == true
+    assert parsed.birthday == %{"year" => 2020, "month" => 1, "day" => 2}
+    assert parsed.custom == %{"pronouns" => "it/its"}
+    assert parsed.about == nil
+  end
+
+  test "create_event validates URLs and NIP-24 fields" do
+    keys = Keys.generate_keypair()
+
+    assert_raise ArgumentError, ~r/Invalid picture URL/, fn ->
+      Metadata.create_event(keys, %{picture: "not a url"})
+    end
+
+    assert_raise ArgumentError, ~r/Invalid bot field/, fn ->
+      Metadata.create_event(keys, %{bot: "yes"})
+    end
+
+    assert_raise ArgumentError, ~r/Invalid birthday field/, fn ->
+      Metadata.create_event(keys, %{birthday: %{month: 13}})
+    end
+  end
+
+  test "merge keeps unknown fields and removes nil fields" do
+    existing = ~s({"name":"alice","about":"old","pronouns":"she/her","nested":{"a":1}})
+
+    merged = Metadata.merge(existing, %{about: "new", name: nil}) |> Jason.decode!()
+
+    assert merged == %{"about" => "new", "pronouns" => "she/her", "nested" => %{"a" => 1}}
+  end
+
+  test "update_event signs a merged profile" do
+    keys = Keys.generate_keypair()
+    original = Metadata.create_event(keys, %{name: "alice", custom_field: "keep me"})
+
+    updated = Metadata.update_event(keys, original, %{display_name: "Alice"})
+
+    assert Event.verify(updated)
+    parsed = Metadata.parse(updated)
+    assert parsed.name == "alice"
+    assert parsed.display_name == "Alice"
+    assert parsed.custom == %{"custom_field" => "keep me"}
+  end
+
+  test "parse rejects other kinds" do
+    keys = Keys.generate_keypair()
+    note_json = Event.create_text_note(keys, "hello")
+
+    assert_raise ArgumentError, ~r/Metadata parse failed:/, fn ->
+      Metadata.parse(note_json)
+    end
+  end
+end
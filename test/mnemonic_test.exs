@@ -42,4 +42,154 @@ defmodule NostrElixir.MnemonicTest do
     assert is_binary(seed)
     assert byte_size(seed) == 64
   end
+
+  describe "languages" do
+    test "generate/2, valid?/2 and to_seed/3 work for every wordlist" do
+      for language <- Mnemonic.languages() do
+        mnemonic = Mnemonic.generate(12, language: language)
+        assert mnemonic.language == language
+        assert Mnemonic.valid?(mnemonic)
+        assert Mnemonic.valid?(mnemonic.phrase, language: language)
+        assert byte_size(Mnemonic.to_seed(mnemonic, "pass")) == 64
+      end
+    end
+
+    test "a phrase is only valid in its own language" do
+      mnemonic = Mnemonic.generate(12, language: :japanese)
+      refute Mnemonic.valid?(mnemonic.phrase)
+      refute Mnemonic.valid?(mnemonic.phrase, language: :korean)
+    end
+
+    test "to_seed/3 matches the BIP-39 Japanese test vector" do
+      phrase =
+        "あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あおぞら"
+
+      seed = Mnemonic.to_seed(phrase, "㍍ガバヴァぱばぐゞちぢ十人十色", language: :japanese)
+
+      assert Base.encode16(seed, case: :lower) ==
+               "a262d6fb6122ecf45be09c50492b31f92e9beb7d9a845987a02cefda57a15f9c467a17872029a9e92299b5cbdf306e3a0ee620245cbd508959b6cb7ca637bd55"
+    end
+
+    test "detect_language/1" do
+      for language <- [:spanish, :korean, :japanese] do
+        mnemonic = Mnemonic.generate(12, language: language)
+        assert Mnemonic.detect_language(mnemonic) == %{language: language, candidates: [language]}
+      end
+
+      assert Mnemonic.detect_language("abandon abandon abandon") ==
+               %{language: nil, candidates: [:english, :french]}
+
+      assert_raise ArgumentError, ~r/Mnemonic detect_language failed:/, fn ->
+        Mnemonic.detect_language("not a real mnemonic")
+      end
+    end
+
+    test "raises on unsupported language" do
+      assert_raise ArgumentError, fn -> Mnemonic.generate(12, language: :klingon) end
+    end
+  end
+
+  describe "recovery helpers" do
+    @valid "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+
+    test "suggest_words/2" do
+      assert Mnemonic.suggest_words("abs") == ["absent", "absorb", "abstract", "absurd"]
+      assert Mnemonic.suggest_words("zzz") == []
+      assert Mnemonic.suggest_words("") == []
+    end
+
+    test "check/2 reports a valid phrase" do
+      assert Mnemonic.check(@valid) == %{
+               valid: true,
+               word_count: 12,
+               word_count_valid: true,
+               unknown_words: [],
+               checksum_error: false
+             }
+    end
+
+    test "check/2 locates unknown words" do
+      result = Mnemonic.check(String.replace(@valid, "about", "abuot"))
+      refute result.valid
+      assert result.unknown_words == [11]
+      refute result.checksum_error
+    end
+
+    test "check/2 detects checksum-only failures" do
+      result = Mnemonic.check(String.duplicate("abandon ", 12))
+      refute result.valid
+      assert result.unknown_words == []
+      assert result.checksum_error
+    end
+
+    test "check/2 reports bad word counts" do
+      result = Mnemonic.check("abandon abandon")
+      refute result.word_count_valid
+      assert result.word_count == 2
+    end
+
+    test "valid_final_words/2" do
+      words = Mnemonic.valid_final_words(String.duplicate("abandon ", 11))
+      assert length(words) == 128
+      assert "about" in words
+      assert words == Mnemonic.valid_final_words(@valid)
+
+      for word <- Enum.take(words, 5) do
+        assert Mnemonic.valid?(String.duplicate("abandon ", 11) <> word)
+      end
+
+      assert_raise ArgumentError, ~r/Mnemonic valid_final_words failed:/, fn ->
+        Mnemonic.valid_final_words("abandon")
+      end
+    end
+  end
+
+  describe "entropy" do
+    test "from_entropy/2 matches the BIP-39 test vectors" do
+      assert Mnemonic.from_entropy("7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f").phrase ==
+               "legal winner thank year wave sausage worth useful legal winner thank yellow"
+
+      assert Mnemonic.from_entropy(String.duplicate("ff", 32)).phrase ==
+               "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo vote"
+    end
+
+    test "to_entropy/2 round trips" do
+      mnemonic = Mnemonic.generate(24, language: :korean)
+      entropy = Mnemonic.to_entropy(mnemonic)
+      assert String.length(entropy) == 64
+      assert Mnemonic.from_entropy(entropy, language: :korean) == mnemonic
+    end
+
+    test "from_entropy/2 raises on invalid entropy length" do
+      assert_raise ArgumentError, ~r/Mnemonic from_entropy failed:/, fn ->
+        Mnemonic.from_entropy("0000")
+      end
+    end
+
+    test "from_dice/3 is deterministic" do
+      rolls = String.duplicate("123456", 9)
+      mnemonic = Mnemonic.from_dice(rolls, 12)
+      assert Mnemonic.valid?(mnemonic)
+      assert Mnemonic.from_dice(rolls, 12) == mnemonic
+      refute Mnemonic.from_dice(rolls <> "1", 12) == mnemonic
+    end
+
+    test "from_dice/3 requires enough valid rolls" do
+      assert_raise ArgumentError, ~r/Not enough dice rolls/, fn ->
+        Mnemonic.from_dice(String.duplicate("123456", 9), 24)
+      end
+
+      assert_raise ArgumentError, ~r/Invalid dice roll/, fn ->
+        Mnemonic.from_dice(String.duplicate("1237", 30), 12)
+      end
+    end
+
+    test "from_dice/3 needs 100 rolls for 24 words" do
+      assert_raise ArgumentError, ~r/99 given, 100 required for 24 words/, fn ->
+        Mnemonic.from_dice(String.duplicate("1", 99), 24)
+      end
+
+      assert Mnemonic.valid?(Mnemonic.from_dice(String.duplicate("1", 100), 24))
+    end
+  end
 end
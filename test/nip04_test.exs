@@ -0,0 +1,64 @@
+defmodule NostrElixir.Nip04Test do
+  use ExUnit.Case, async: true
+  alias NostrElixir.Nip04
+
+  setup do
+    %{alice: NostrElixir.Keys.generate_keypair(), bob: NostrElixir.Keys.generate_keypair()}
+  end
+
+  describe "encrypt/3 and decrypt/3" do
+    test "round trip", %{alice: alice, bob: bob} do
+      ciphertext = Nip04.encrypt(alice.secret_key, bob.public_key, "hello")
+      assert ciphertext =~ "?iv="
+      assert Nip04.decrypt(bob.secret_key, alice.public_key, ciphertext) == "hello"
+    end
+
+    test "raises on invalid ciphertext", %{alice: alice, bob: bob} do
+      assert_raise ArgumentError, ~r/NIP-04 decrypt failed:/, fn ->
+        Nip04.decrypt(bob.secret_key, alice.public_key, "not-a-ciphertext")
+      end
+    end
+  end
+
+  describe "create_dm_event/4 and decrypt_dm_event/2" do
+    test "builds a signed kind 4 event with a p tag", %{alice: alice, bob: bob} do
+      event_json = Nip04.create_dm_event(alice, bob.public_key, "hello", reply_to: String.duplicate("a", 64))
+      event = Jason.decode!(event_json)
+      assert event["kind"] == 4
+      assert event["pubkey"] == alice.public_key
+      assert ["p", bob.public_key] in event["tags"]
+      assert ["e", String.duplicate("a", 64)] in event["tags"]
+      assert event["content"] =~ "?iv="
+      assert NostrElixir.Event.verify(event_json)
+    end
+
+    test "both participants can decrypt", %{alice: alice, bob: bob} do
+      event_json = Nip04.create_dm_event(alice, bob.public_key, "hello")
+
+      received = Nip04.decrypt_dm_event(bob, event_json)
+      assert received.plaintext == "hello"
+      assert received.sender == alice.public_key
+      assert received.peer == alice.public_key
+
+      sent = Nip04.decrypt_dm_event(alice, event_json)
+      assert sent.plaintext == "hello"
+      assert sent.receiver == bob.public_key
+      assert sent.peer == bob.public_key
+    end
+
+    test "raises for non-participants and other kinds", %{alice: alice, bob: bob} do
+      event_json = Nip04.create_dm_event(alice, bob.public_key, "hello")
+      stranger = NostrElixir.Keys.generate_keypair()
+
+      assert_raise ArgumentError, ~r/NIP-04 decrypt_dm_event failed:/, fn ->
+        Nip04.decrypt_dm_event(stranger, event_json)
+      end
+
+      note = NostrElixir.Event.create_text_note(alice, "hi")
+
+      assert_raise ArgumentError, ~r/NIP-04 decrypt_dm_event failed:/, fn ->
+        Nip04.decrypt_dm_event(bob, note)
+      end
+    end
+  end
+end
@@ -0,0 +1,82 @@
+defmodule NostrElixir.Nip05Test do
+  use ExUnit.Case, async: true
+  alias NostrElixir.Nip05
+
+  @bob_pubkey "b0635d6a9851d3aed0cd6c495b282167acf761729078d975fc341b22650b07b9"
+  @nostr_json """
+  {
+    "names": {
+      "bob": "b0635d6a9851d3aed0cd6c495b282167acf761729078d975fc341b22650b07b9",
+      "_": "b0635d6a9851d3aed0cd6c495b282167acf761729078d975fc341b22650b07b9"
+    },
+    "relays": {
+      "b0635d6a9851d3aed0cd6c495b282167acf761729078d975fc341b22650b07b9": ["wss://relay.example.com", "wss://relay2.example.com"]
+    }
+  }
+  """
+
+  describe "parse/1" do
+    test "splits name and domain and builds the well-known URL" do
+      assert Nip05.parse("Bob@Example.com") == %{
+               name: "bob",
+               domain: "example.com",
+               url: "https://example.com/.well-known/nostr.json?name=bob"
+             }
+    end
+
+    test "treats a bare domain as the root identifier" do
+      assert Nip05.parse("example.com").name == "_"
+      assert Nip05.well_known_url("example.com") == "https://example.com/.well-known/nostr.json?name=_"
+    end
+
+    test "rejects invalid identifiers" do
+      for identifier <- ["bo b@example.com", "bob@", "bob@exa/mple.com"] do
+        assert_raise ArgumentError, ~r/NIP-05 parse failed:/, fn -> Nip05.parse(identifier) end
+      end
+    end
+  end
+
+  describe "verify/3" do
+    test "checks the name maps to the public key" do
+      assert Nip05.verify("bob@example.com", @bob_pubkey, @nostr_json)
+      assert Nip05.verify("example.com", @bob_pubkey, @nostr_json)
+      refute Nip05.verify("alice@example.com", @bob_pubkey, @nostr_json)
+      refute Nip05.verify("bob@example.com", "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798", @nostr_json)
+    end
+  end
+
+  describe "profile/2" do
+    test "returns the public key and relays" do
+      profile = Nip05.profile("bob@example.com", @nostr_json)
+
+      assert profile.public_key == @bob_pubkey
+      assert profile.relays == ["wss://relay.example.com", "wss://relay2.example.com"]
+      assert profile.nip46 == []
+    end
+
+    test "raises when the name is missing" do
+      assert_raise ArgumentError, ~r/NIP-05 profile failed:/, fn ->
+        Nip05.profile("alice@example.com", @nostr_json)
+      end
+    end
+  end
+
+  describe "build_document/1" do
+    test "builds names and relays" do
+      document = Nip05.build_document([{"Bob", @bob_pubkey, ["wss://relay.example.com"]}, {"_", @bob_pubkey}])
+
+      assert Jason.decode!(document) == %{
+               "names" => %{"bob" => @bob_pubkey, "_" => @bob_pubkey},
+               "relays" => %{@bob_pubkey => ["wss://relay.example.com"]}
+             }
+
+      assert Nip05.verify("bob@example.com", @bob_pubkey, document)
+    end
+
+    test "rejects invalid public keys" do
+      assert_raise ArgumentError, ~r/NIP-05 build_document failed:/, fn ->
+        Nip05.build_document([{"bob", "not-a-key"}])
+      end
+    end
+  end
+end
@@ -14,4 +14,100 @@ defmodule NostrElixir.Nip06Test do
     assert String.length(result["secret_key"]) == 64
     assert result["derivation_path"] == path
   end
+
+  describe "derive_account/2" do
+    test "matches the NIP-06 test vectors" do
+      account =
+        Nip06.derive_account("leader monkey parrot ring guide accident before fence cannon height naive bean")
+
+      assert account.secret_key == "7f7ff03d123792d6ac594bfa67bf6d0c0ab55b6b1fdb6249303fe861f1ccba9a"
+      assert account.public_key == "17162c921dc4d2518f9a101db33695df1afb56ab82f5ff3e5da6eec3ca5cd917"
+      assert account.nsec == "nsec10allq0gjx7fddtzef0ax00mdps9t2kmtrldkyjfs8l5xruwvh2dq0lhhkp"
+      assert account.npub == "npub1zutzeysacnf9rru6zqwmxd54mud0k44tst6l70ja5mhv8jjumytsd2x7nu"
+      assert account.derivation_path == "m/44'/1237'/0'/0/0"
+
+      phrase =
+        "what bleak badge arrange retreat wolf trade produce cricket blur garlic valid proud rude strong choose busy staff weather area salt hollow arm fade"
+
+      account = Nip06.derive_account(phrase)
+      assert account.secret_key == "c15d739894c81a2fcfd3a2df85a0d2c0dbc47a280d092799f144d73d7ae78add"
+      assert account.public_key == "d41b22899549e1f3d335a31002cfd382174006e166d3e658e3a5eecdb6463573"
+    end
+
+    test "derives distinct accounts and honours the passphrase" do
+      mnemonic = Mnemonic.generate(12)
+      account0 = Nip06.derive_account(mnemonic)
+      account1 = Nip06.derive_account(mnemonic, account: 1)
+      with_passphrase = Nip06.derive_account(mnemonic, passphrase: "secret")
+
+      assert account1.account == 1
+      assert account1.derivation_path == "m/44'/1237'/1'/0/0"
+      refute account0.public_key == account1.public_key
+      refute account0.public_key == with_passphrase.public_key
+      assert NostrElixir.Keys.parse_keypair(account0.secret_key).public_key == account0.public_key
+    end
+
+    test "uses the language of the mnemonic" do
+      mnemonic = Mnemonic.generate(12, language: :spanish)
+      account = Nip06.derive_account(mnemonic)
+      assert account == Nip06.derive_account(mnemonic.phrase, language: :spanish)
+    end
+
+    test "raises on an invalid mnemonic" do
+      assert_raise ArgumentError, ~r/NIP-06 derive_account failed:/, fn ->
+        Nip06.derive_account("not a real mnemonic")
+      end
+    end
+  end
+
+  describe "Keychain" do
+    alias NostrElixir.Nip06.Keychain
+
+    @phrase "leader monkey parrot ring guide accident before fence cannon height naive bean"
+
+    test "derives the same accounts as derive_account/2" do
+      keychain = Keychain.new(@phrase, passphrase: "pass")
+
+      for index <- [0, 1, 7] do
+        assert Keychain.account(keychain, index) ==
+                 Nip06.derive_account(@phrase, passphrase: "pass", account: index)
+      end
+    end
+
+    test "lists accounts" do
+      keychain = Keychain.new(@phrase)
+      [first | _] = accounts = Keychain.list(keychain, 3)
+      assert Enum.map(accounts, & &1.account) == [0, 1, 2]
+      assert first.public_key == "17162c921dc4d2518f9a101db33695df1afb56ab82f5ff3e5da6eec3ca5cd917"
+      assert first.npub == "npub1zutzeysacnf9rru6zqwmxd54mud0k44tst6l70ja5mhv8jjumytsd2x7nu"
+      assert Enum.map(Keychain.list(keychain, 2, start: 1), & &1.account) == [1, 2]
+    end
+
+    test "finds the account of a pubkey" do
+      keychain = Keychain.new(Mnemonic.from_phrase(@phrase))
+      account = Keychain.account(keychain, 5)
+      assert Keychain.find_account(keychain, account.public_key) == 5
+      assert Keychain.find_account(keychain, account.npub) == 5
+      assert Keychain.find_account(keychain, account.public_key, max_accounts: 5) == nil
+      assert Keychain.find_account(keychain, NostrElixir.Keys.generate_keypair().public_key) == nil
+    end
+
+    test "raises on an invalid mnemonic" do
+      assert_raise ArgumentError, ~r/NIP-06 keychain failed:/, fn ->
+        Keychain.new("not a real mnemonic")
+      end
+    end
+
+    test "refuses to derive more than 1000 accounts at once" do
+      keychain = Keychain.new(@phrase)
+
+      assert_raise ArgumentError, ~r/NIP-06 keychain list failed: At most 1000 accounts/, fn ->
+        Keychain.list(keychain, 4_000_000_000)
+      end
+
+      assert_raise ArgumentError, ~r/NIP-06 keychain find_account failed: At most 1000 accounts/, fn ->
+        Keychain.find_account(keychain, NostrElixir.Keys.generate_keypair().public_key, max_accounts: 1001)
+      end
+    end
+  end
 end
@@ -192,4 +192,77 @@ defmodule NostrElixir.Nip09Test do
     assert deletion.event_ids == original_event_ids
     assert deletion.reason == original_reason
   end
+
+  test "extract_deletion extracts coordinates and kinds" do
+    event_json = """
+    {
+      "kind": 5,
+      "content": "",
+      "tags": [
+        ["e", "event_id_1"],
+        ["a", "30023:author:article"],
+        ["k", "1"],
+        ["k", "30023"]
+      ]
+    }
+    """
+
+    deletion = Nip09.extract_deletion(event_json)
+
+    assert deletion.event_ids == ["event_id_1"]
+    assert deletion.coordinates == ["30023:author:article"]
+    assert deletion.kinds == [1, 30023]
+  end
+
+  test "create_deletion_request emits e, a and k tags" do
+    keys = Keys.generate_keypair()
+    event_id = String.duplicate("a", 64)
+    coordinate = "30023:#{keys.public_key}:my-article"
+
+    event_json = Nip09.create_deletion_request(keys, event_ids: [event_id], coordinates: [coordinate], kinds: [1], reason: "oops")
+
+    assert Event.verify(event_json)
+    event = Jason.decode!(event_json)
+    assert event["kind"] == 5
+    assert event["content"] == "oops"
+    assert ["e", event_id] in event["tags"]
+    assert ["a", coordinate] in event["tags"]
+    assert ["k", "1"] in event["tags"]
+    assert ["k", "30023"] in event["tags"]
+  end
+
+  test "create_deletion_request rejects another author's coordinate" do
+    keys = Keys.generate_keypair()
+    other = Keys.generate_keypair()
+
+    assert_raise ArgumentError, ~r/NIP-09 create_deletion_request failed:/, fn ->
+      Nip09.create_deletion_request(keys, coordinates: ["30023:#{other.public_key}:article"])
+    end
+  end
+
+  test "apply_deletion only removes the author's own events" do
+    alice = Keys.generate_keypair()
+    mallory = Keys.generate_keypair()
+    alice_note = NostrElixir.Nip10.create_text_note(alice, "Hello")
+    alice_id = Jason.decode!(alice_note)["id"]
+
+    # Mallory tries to delete Alice's note
+    forged = Nip09.create_deletion_request(mallory, event_ids: [alice_id])
+    assert {[^alice_note], []} = Nip09.apply_deletion(forged, [alice_note])
+
+    deletion = Nip09.create_deletion_for_events(alice, [alice_note], "typo")
+    assert {[], [^alice_id]} = Nip09.apply_deletion(deletion, [alice_note])
+  end
+
+  test "apply_deletion deletes addressable events by coordinate" do
+    keys = Keys.generate_keypair()
+    article = Event.new(keys.public_key, "v1", 30023, [["d", "article"]]) |> Event.sign(keys.secret_key)
+    other_article = Event.new(keys.public_key, "other", 30023, [["d", "other"]]) |> Event.sign(keys.secret_key)
+
+    deletion = Nip09.create_deletion_request(keys, coordinates: ["30023:#{keys.public_key}:article"])
+    {kept, deleted_ids} = Nip09.apply_deletion(deletion, [article, other_article, deletion])
+
+    assert deleted_ids == [Jason.decode!(article)["id"]]
+    assert kept == [other_article, deletion]
+  end
 end
@@ -32,4 +32,56 @@ defmodule NostrElixir.Nip17Test do
       end
     end
   end
+
+  describe "send_private_msg/4 and unwrap/2" do
+    setup do
+      %{alice: NostrElixir.Keys.generate_keypair(), bob: NostrElixir.Keys.generate_keypair()}
+    end
+
+    test "wraps once per recipient plus once for the sender", %{alice: alice, bob: bob} do
+      %{gift_wraps: wraps} = Nip17.send_private_msg(alice, [bob.public_key], "hello")
+      assert Enum.sort(Enum.map(wraps, & &1.receiver)) == Enum.sort([alice.public_key, bob.public_key])
+
+      for wrap <- wraps do
+        event = Jason.decode!(wrap.event)
+        assert event["kind"] == 1059
+        refute event["pubkey"] in [alice.public_key, bob.public_key]
+        assert ["p", wrap.receiver] in event["tags"]
+      end
+    end
+
+    test "both participants can unwrap the message", %{alice: alice, bob: bob} do
+      %{rumor_id: rumor_id, gift_wraps: wraps} =
+        Nip17.send_private_msg(alice, [bob.public_key], "hello", subject: "Plans")
+
+      bob_wrap = Enum.find(wraps, &(&1.receiver == bob.public_key))
+      alice_wrap = Enum.find(wraps, &(&1.receiver == alice.public_key))
+
+      for {keys, wrap} <- [{bob, bob_wrap}, {alice, alice_wrap}] do
+        msg = Nip17.unwrap(keys, wrap.event)
+        assert msg.sender == alice.public_key
+        assert msg.message == "hello"
+        assert msg.kind == 14
+        assert msg.subject == "Plans"
+        assert msg.participants == [alice.public_key, bob.public_key]
+        assert msg.rumor["id"] == rumor_id
+      end
+    end
+
+    test "raises when unwrapping with the wrong key", %{alice: alice, bob: bob} do
+      %{gift_wraps: wraps} = Nip17.send_private_msg(alice, [bob.public_key], "hello")
+      wrap = Enum.find(wraps, &(&1.receiver == bob.public_key))
+      stranger = NostrElixir.Keys.generate_keypair()
+
+      assert_raise ArgumentError, ~r/NIP-17 unwrap failed:/, fn ->
+        Nip17.unwrap(stranger, wrap.event)
+      end
+    end
+
+    test "raises without receivers", %{alice: alice} do
+      assert_raise ArgumentError, ~r/NIP-17 send_private_msg failed:/, fn ->
+        Nip17.send_private_msg(alice, [], "hello")
+      end
+    end
+  end
 end
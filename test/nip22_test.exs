@@ -0,0 +1,83 @@
+defmodule NostrElixir.Nip22Test do
+  use ExUnit.Case, async: true
+  alias NostrElixir.{Keys, Nip10, Nip22, Nip23, Event}
+
+  setup do
+    author = Keys.generate_keypair()
+    commenter = Keys.generate_keypair()
+    article_json = Nip23.create_long_form(author, "# Article", %{title: "Article"})
+    %{author: author, commenter: commenter, article_json: article_json}
+  end
+
+  test "create_comment on an article uses A/a scope tags", %{author: author, commenter: commenter, article_json: article_json} do
+    comment_json = Nip22.create_comment(commenter, "Great read!", article_json, relay_url: "wss://relay.damus.io")
+
+    assert Event.verify(comment_json)
+
+    event = Jason.decode!(comment_json)
+    article = Jason.decode!(article_json)
+    coordinate = "30023:#{author.public_key}:"
+    assert event["kind"] == 1111
+    assert ["A", coordinate, "wss://relay.damus.io"] in event["tags"]
+    assert ["K", "30023"] in event["tags"]
+    assert ["P", author.public_key, "wss://relay.damus.io"] in event["tags"]
+    assert ["a", coordinate, "wss://relay.damus.io"] in event["tags"]
+    assert ["e", article["id"], "wss://relay.damus.io", author.public_key] in event["tags"]
+    assert ["k", "30023"] in event["tags"]
+  end
+
+  test "create_comment on a regular event uses E/e scope tags", %{author: author, commenter: commenter} do
+    note_json = Nip10.create_text_note(author, "Hello")
+    note_id = Jason.decode!(note_json)["id"]
+
+    comment = Nip22.create_comment(commenter, "Hi", note_json) |> Nip22.parse_comment()
+
+    assert comment.root.type == "event"
+    assert comment.root.event_id == note_id
+    assert comment.root.kind == "1"
+    assert comment.root.pubkey == author.public_key
+    assert comment.parent == comment.root
+  end
+
+  test "reply to a comment keeps the root scope", %{author: author, commenter: commenter, article_json: article_json} do
+    comment_json = Nip22.create_comment(commenter, "Great read!", article_json)
+    comment_id = Jason.decode!(comment_json)["id"]
+
+    reply = Nip22.create_comment(author, "Thanks!", comment_json) |> Nip22.parse_comment()
+
+    assert reply.root.type == "address"
+    assert reply.root.coordinate == "30023:#{author.public_key}:"
+    assert reply.root.kind == "30023"
+    assert reply.parent.type == "event"
+    assert reply.parent.event_id == comment_id
+    assert reply.parent.kind == "1111"
+    assert reply.parent.pubkey == commenter.public_key
+  end
+
+  test "create_external_comment on a URL", %{commenter: commenter} do
+    comment_json = Nip22.create_external_comment(commenter, "Nice post", "https://example.com/post")
+
+    event = Jason.decode!(comment_json)
+    assert ["I", "https://example.com/post"] in event["tags"]
+    assert ["K", "web"] in event["tags"]
+    assert ["i", "https://example.com/post"] in event["tags"]
+    assert ["k", "web"] in event["tags"]
+
+    comment = Nip22.parse_comment(comment_json)
+    assert comment.root.type == "external"
+    assert comment.root.external == "https://example.com/post"
+  end
+
+  test "create_external_comment with explicit kind", %{commenter: commenter} do
+    comment = Nip22.create_external_comment(commenter, "Loved it", "isbn:9780765382030", kind: "isbn") |> Nip22.parse_comment()
+
+    assert comment.root.external == "isbn:9780765382030"
+    assert comment.root.kind == "isbn"
+  end
+
+  test "parse_comment rejects non-comment events", %{article_json: article_json} do
+    assert_raise ArgumentError, ~r/NIP-22 parse_comment failed:/, fn ->
+      Nip22.parse_comment(article_json)
+    end
+  end
+end
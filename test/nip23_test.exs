@@ -209,4 +209,72 @@ defmodule NostrElixir.Nip23Test do
     tags = Nip23.build_long_form_tags(opts)
     assert tags == [["title", "Test"]]
   end
+
+  test "create_article builds a kind 30023 event with a d tag" do
+    keys = Keys.generate_keypair()
+    opts = %{
+      identifier: "my-article",
+      title: "My Article",
+      summary: "A summary",
+      image: "https://example.com/image.jpg",
+      published_at: 1234567890,
+      hashtags: ["nostr", "elixir"]
+    }
+
+    event_json = Nip23.create_article(keys, "# My Article", opts)
+
+    assert Event.verify(event_json)
+    event = Jason.decode!(event_json)
+    assert event["kind"] == 30023
+    assert ["d", "my-article"] in event["tags"]
+    assert ["title", "My Article"] in event["tags"]
+    assert ["published_at", "1234567890"] in event["tags"]
+    assert ["t", "nostr"] in event["tags"]
+  end
+
+  test "create_article requires an identifier" do
+    keys = Keys.generate_keypair()
+
+    assert_raise ArgumentError, ~r/NIP-23 create_long_form failed:/, fn ->
+      Nip23.create_article(keys, "content", %{title: "No identifier"})
+    end
+  end
+
+  test "create_draft builds a kind 30024 event" do
+    keys = Keys.generate_keypair()
+
+    event_json = Nip23.create_draft(keys, "WIP", identifier: "draft-1", title: "Draft")
+
+    assert Jason.decode!(event_json)["kind"] == 30024
+    article = Nip23.parse_article(event_json)
+    assert article.draft
+    assert article.coordinate == "30024:#{keys.public_key}:draft-1"
+  end
+
+  test "parse_article round-trips fields, coordinate and naddr" do
+    keys = Keys.generate_keypair()
+    opts = %{identifier: "my-article", title: "Title", summary: "Summary", published_at: 1234567890, hashtags: ["nostr"]}
+    event_json = Nip23.create_article(keys, "Body", opts)
+
+    article = Nip23.parse_article(event_json, ["wss://relay.damus.io"])
+
+    assert article.identifier == "my-article"
+    assert article.title == "Title"
+    assert article.summary == "Summary"
+    assert article.published_at == 1234567890
+    assert article.hashtags == ["nostr"]
+    assert article.content == "Body"
+    refute article.draft
+    assert article.coordinate == "30023:#{keys.public_key}:my-article"
+    assert String.starts_with?(article.naddr, "naddr1")
+  end
+
+  test "parse_article rejects other kinds" do
+    keys = Keys.generate_keypair()
+    note_json = NostrElixir.Nip10.create_text_note(keys, "not an article")
+
+    assert_raise ArgumentError, ~r/NIP-23 parse_article failed:/, fn ->
+      Nip23.parse_article(note_json)
+    end
+  end
 end
@@ -0,0 +1,95 @@
+defmodule NostrElixir.Nip25Test do
+  use ExUnit.Case, async: true
+  alias NostrElixir.{Keys, Nip10, Nip25, Event}
+
+  setup do
+    author = Keys.generate_keypair()
+    reactor = Keys.generate_keypair()
+    note_json = Nip10.create_text_note(author, "Hello, Nostr!")
+    %{author: author, reactor: reactor, note_json: note_json}
+  end
+
+  test "create_reaction creates a valid like", %{author: author, reactor: reactor, note_json: note_json} do
+    reaction_json = Nip25.create_reaction(reactor, note_json, "+", relay_url: "wss://relay.damus.io")
+
+    assert Event.verify(reaction_json)
+
+    event = Jason.decode!(reaction_json)
+    note = Jason.decode!(note_json)
+    assert event["kind"] == 7
+    assert event["content"] == "+"
+    assert ["e", note["id"], "wss://relay.damus.io", author.public_key] in event["tags"]
+    assert ["p", author.public_key] in event["tags"]
+    assert ["k", "1"] in event["tags"]
+  end
+
+  test "create_reaction adds an a tag for addressable events", %{author: author, reactor: reactor} do
+    article_json = Event.new(author.public_key, "Article", 30023, [["d", "my-article"]]) |> Event.sign(author.secret_key)
+
+    reaction = Nip25.create_reaction(reactor, article_json, "-") |> Nip25.parse_reaction()
+
+    assert reaction.reaction_type == "dislike"
+    assert reaction.coordinate == "30023:#{author.public_key}:my-article"
+    assert reaction.target == reaction.coordinate
+    assert reaction.target_kind == 30023
+  end
+
+  test "custom emoji reactions carry an emoji tag", %{reactor: reactor, note_json: note_json} do
+    reaction_json = Nip25.create_reaction(reactor, note_json, ":soap:", emoji_url: "https://example.com/soap.png")
+
+    event = Jason.decode!(reaction_json)
+    assert ["emoji", "soap", "https://example.com/soap.png"] in event["tags"]
+
+    reaction = Nip25.parse_reaction(reaction_json)
+    assert reaction.reaction_type == "custom_emoji"
+    assert reaction.shortcode == "soap"
+    assert reaction.emoji_url == "https://example.com/soap.png"
+  end
+
+  test "custom emoji reaction without url raises", %{reactor: reactor, note_json: note_json} do
+    assert_raise ArgumentError, ~r/NIP-25 create_reaction failed:/, fn ->
+      Nip25.create_reaction(reactor, note_json, ":soap:")
+    end
+  end
+
+  test "parse_reaction returns target, author and kind", %{author: author, reactor: reactor, note_json: note_json} do
+    reaction = Nip25.create_reaction(reactor, note_json, "🤙") |> Nip25.parse_reaction()
+    note = Jason.decode!(note_json)
+
+    assert reaction.reaction_type == "emoji"
+    assert reaction.content == "🤙"
+    assert reaction.pubkey == reactor.public_key
+    assert reaction.event_id == note["id"]
+    assert reaction.target == note["id"]
+    assert reaction.author == author.public_key
+    assert reaction.target_kind == 1
+  end
+
+  test "create_website_reaction creates a kind 17 event", %{reactor: reactor} do
+    reaction_json = Nip25.create_website_reaction(reactor, "https://example.com/post")
+
+    assert Event.verify(reaction_json)
+    reaction = Nip25.parse_reaction(reaction_json)
+    assert reaction.kind == 17
+    assert reaction.url == "https://example.com/post"
+    assert reaction.reaction_type == "like"
+  end
+
+  test "parse_reaction rejects non-reaction events", %{note_json: note_json} do
+    assert_raise ArgumentError, ~r/NIP-25 parse_reaction failed:/, fn ->
+      Nip25.parse_reaction(note_json)
+    end
+  end
+
+  test "aggregate counts each author once per target and reaction", %{author: author, reactor: reactor, note_json: note_json} do
+    note_id = Jason.decode!(note_json)["id"]
+    like = Nip25.create_reaction(reactor, note_json, "+")
+    like_again = Nip25.create_reaction(reactor, note_json, "")
+    own_like = Nip25.create_reaction(author, note_json, "+")
+    fire = Nip25.create_reaction(reactor, note_json, "🔥")
+
+    counts = Nip25.aggregate([like, like_again, own_like, fire, note_json])
+
+    assert counts == %{note_id => %{"+" => 2, "🔥" => 1}}
+  end
+end
@@ -23,4 +23,77 @@ defmodule NostrElixir.Nip44Test do
       Nip44.decrypt(keys3.secret_key, keys1.public_key, ciphertext)
     end
   end
+
+  describe "conversation keys" do
+    test "matches the NIP-44 get_conversation_key test vector" do
+      key =
+        Nip44.conversation_key(
+          "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364139",
+          "0000000000000000000000000000000000000000000000000000000000000002"
+        )
+
+      assert Nip44.conversation_key_to_hex(key) ==
+               "8b6392dbf2ec6a2b2d5b1477fc2be84d63ef254b667cadd31bd3f444c44ae6ba"
+    end
+
+    test "is shared by both peers and interoperates with encrypt/decrypt" do
+      keys1 = Keys.generate_keypair()
+      keys2 = Keys.generate_keypair()
+      key1 = Nip44.conversation_key(keys1.secret_key, keys2.public_key)
+      key2 = Nip44.conversation_key(keys2.secret_key, keys1.public_key)
+      assert Nip44.conversation_key_to_hex(key1) == Nip44.conversation_key_to_hex(key2)
+
+      ciphertext = Nip44.encrypt_with_key(key1, "cached")
+      assert Nip44.decrypt(keys2.secret_key, keys1.public_key, ciphertext) == "cached"
+
+      ciphertext = Nip44.encrypt(keys2.secret_key, keys1.public_key, "uncached")
+      assert Nip44.decrypt_with_key(key1, ciphertext) == "uncached"
+    end
+
+    test "round-trips through hex" do
+      keys1 = Keys.generate_keypair()
+      keys2 = Keys.generate_keypair()
+      key = Nip44.conversation_key(keys1.secret_key, keys2.public_key)
+      imported = key |> Nip44.conversation_key_to_hex() |> Nip44.conversation_key_from_hex()
+      assert Nip44.decrypt_with_key(imported, Nip44.encrypt_with_key(key, "hi")) == "hi"
+    end
+
+    test "raises on invalid input" do
+      assert_raise ArgumentError, ~r/NIP-44 conversation_key_from_hex failed/, fn ->
+        Nip44.conversation_key_from_hex("abcd")
+      end
+
+      keys1 = Keys.generate_keypair()
+      keys2 = Keys.generate_keypair()
+      key = Nip44.conversation_key(keys1.secret_key, keys2.public_key)
+
+      assert_raise ArgumentError, ~r/NIP-44 decrypt failed/, fn ->
+        Nip44.decrypt_with_key(key, "not-a-payload")
+      end
+    end
+  end
+
+  describe "decrypt_auto/3" do
+    test "detects NIP-44 and NIP-04 payloads" do
+      keys1 = Keys.generate_keypair()
+      keys2 = Keys.generate_keypair()
+
+      nip44 = Nip44.encrypt(keys1.secret_key, keys2.public_key, "modern")
+      assert Nip44.decrypt_auto(keys2.secret_key, keys1.public_key, nip44) ==
+               %{scheme: :nip44, version: 2, plaintext: "modern"}
+
+      nip04 = NostrElixir.Nip04.encrypt(keys1.secret_key, keys2.public_key, "legacy")
+      assert Nip44.decrypt_auto(keys2.secret_key, keys1.public_key, nip04) ==
+               %{scheme: :nip04, version: nil, plaintext: "legacy"}
+    end
+
+    test "raises on unsupported payloads" do
+      keys1 = Keys.generate_keypair()
+      keys2 = Keys.generate_keypair()
+
+      assert_raise ArgumentError, ~r/NIP-44 decrypt_auto failed/, fn ->
+        Nip44.decrypt_auto(keys2.secret_key, keys1.public_key, "#unsupported")
+      end
+    end
+  end
 end
@@ -0,0 +1,256 @@
+defmodule NostrElixir.Nip47Test do
+  use ExUnit.Case, async: true
+  alias NostrElixir.{Event, Nip04, Nip44, Nip47}
+
+  @wallet_secret_key "5c0c523f52a5b6fad39ed2403092df8cebc36318b39383bca6c00808626fab3a"
+  @wallet_pubkey "87d3561f19b74adbe8bf840682992466068830a9d8c36b4a0c99d36f826cb6cb"
+  @client_secret_key "4b22aa260e4acb7021e32f38a6cdf4b673c6a277755bfce287e370c924dc936d"
+  @client_pubkey "fa3b4f81a620c66514bda0302847df167ed02a483141b5939e57bdd0cf76ad3b"
+
+  @uri "nostr+walletconnect://#{@wallet_pubkey}?relay=wss%3A%2F%2Frelay.damus.io&secret=#{@client_secret_key}&lud16=alice@example.com"
+
+  defp wallet_event(kind, content, tags) do
+    Event.new(@wallet_pubkey, content, kind, tags) |> Event.sign(@wallet_secret_key)
+  end
+
+  defp client_request(body, encrypt, tags) do
+    content = encrypt.(@client_secret_key, @wallet_pubkey, Jason.encode!(body))
+    Event.new(@client_pubkey, content, 23194, [["p", @wallet_pubkey] | tags]) |> Event.sign(@client_secret_key)
+  end
+
+  defp response(payload, request_id, encrypt \\ &Nip04.encrypt/3) do
+    wallet_event(23195, encrypt.(@wallet_secret_key, @client_pubkey, Jason.encode!(payload)), [["p", @client_pubkey], ["e", request_id]])
+  end
+
+  describe "parse_uri/1" do
+    test "extracts the wallet, relays, secret and lud16" do
+      conn = Nip47.parse_uri(@uri)
+      assert conn.wallet_pubkey == @wallet_pubkey
+      assert conn.relays == ["wss://relay.damus.io"]
+      assert conn.secret == @client_secret_key
+      assert conn.client_pubkey == @client_pubkey
+      assert conn.lud16 == "alice@example.com"
+    end
+
+    test "rejects other URIs" do
+      assert_raise ArgumentError, ~r/NIP-47 parse_uri failed:/, fn -> Nip47.parse_uri("https://example.com") end
+    end
+  end
+
+  describe "parse_info/2 and encryption/1" do
+    test "reads methods, notifications and encryption schemes" do
+      info =
+        wallet_event(13194, "pay_invoice get_balance make_invoice", [["encryption", "nip44_v2 nip04"], ["notifications", "payment_received payment_sent"]])
+        |> then(&Nip47.parse_info(@uri, &1))
+
+      assert info.wallet_pubkey == @wallet_pubkey
+      assert info.methods == [:pay_invoice, :get_balance, :make_invoice]
+      assert info.notifications == [:payment_received, :payment_sent]
+      assert info.encryption == [:nip44_v2, :nip04]
+      assert Nip47.encryption(info) == :nip44_v2
+    end
+
+    test "falls back to NIP-04 without an encryption tag" do
+      info = Nip47.parse_info(@uri, wallet_event(13194, "pay_invoice", []))
+      assert info.encryption == [:nip04]
+      assert Nip47.encryption(info) == :nip04
+    end
+
+    test "rejects info events not signed by the wallet" do
+      forged = Event.new(@client_pubkey, "pay_invoice", 13194, []) |> Event.sign(@client_secret_key)
+
+      assert_raise ArgumentError, ~r/NIP-47 parse_info failed: Event is not from the connected wallet/, fn ->
+        Nip47.parse_info(@uri, forged)
+      end
+    end
+  end
+
+  describe "requests" do
+    setup do
+      %{conn: Nip47.parse_uri(@uri)}
+    end
+
+    test "pay_invoice/3 encrypts to the wallet with NIP-44", %{conn: conn} do
+      event = Jason.decode!(Nip47.pay_invoice(conn, "lnbc1...", encryption: :nip44_v2, expiration: 1_800_000_000))
+      assert event["kind"] == 23194
+      assert event["pubkey"] == @client_pubkey
+      assert ["p", @wallet_pubkey] in event["tags"]
+      assert ["encryption", "nip44_v2"] in event["tags"]
+      assert ["expiration", "1800000000"] in event["tags"]
+
+      assert Jason.decode!(Nip44.decrypt(@wallet_secret_key, @client_pubkey, event["content"])) ==
+               %{"method" => "pay_invoice", "params" => %{"invoice" => "lnbc1..."}}
+    end
+
+    test "requests default to NIP-04 without an encryption tag", %{conn: conn} do
+      event = Jason.decode!(Nip47.get_balance(conn))
+      refute Enum.any?(event["tags"], &match?(["encryption" | _], &1))
+      assert Jason.decode!(Nip04.decrypt(@wallet_secret_key, @client_pubkey, event["content"]))["method"] == "get_balance"
+    end
+
+    test "builds the other methods", %{conn: conn} do
+      params = fn json -> Jason.decode!(Nip04.decrypt(@wallet_secret_key, @client_pubkey, Jason.decode!(json)["content"]))["params"] end
+
+      assert params.(Nip47.make_invoice(conn, 21000, description: "coffee")) == %{"amount" => 21000, "description" => "coffee"}
+      assert params.(Nip47.lookup_invoice(conn, payment_hash: "ab")) == %{"payment_hash" => "ab"}
+      assert params.(Nip47.list_transactions(conn, limit: 10, type: :incoming)) == %{"limit" => 10, "type" => "incoming"}
+      assert params.(Nip47.pay_keysend(conn, 1000, "03ab")) == %{"amount" => 1000, "pubkey" => "03ab"}
+    end
+
+    test "rejects params that do not match the method", %{conn: conn} do
+      assert_raise ArgumentError, ~r/NIP-47 pay_invoice failed:/, fn -> Nip47.pay_invoice(conn, nil) end
+    end
+  end
+
+  describe "parse_response/2" do
+    setup do
+      %{conn: Nip47.parse_uri(@uri), request_id: String.duplicate("ab", 32)}
+    end
+
+    test "parses a NIP-04 get_balance result", %{conn: conn, request_id: request_id} do
+      response = Nip47.parse_response(conn, response(%{result_type: "get_balance", result: %{balance: 21000}}, request_id))
+      assert response.request_id == request_id
+      assert response.result_type == :get_balance
+      assert response.error == nil
+      assert response.result == %{balance: 21000}
+    end
+
+    test "parses NIP-44 wallet errors", %{conn: conn, request_id: request_id} do
+      payload = %{result_type: "pay_invoice", error: %{code: "INSUFFICIENT_BALANCE", message: "no funds"}, result: nil}
+      response = Nip47.parse_response(conn, response(payload, request_id, &Nip44.encrypt/3))
+      assert response.error == %{code: :insufficient_balance, message: "no funds"}
+      assert response.result == nil
+    end
+
+    test "parses transactions", %{conn: conn, request_id: request_id} do
+      tx = %{type: "incoming", invoice: "lnbc1...", payment_hash: "11", amount: 1000, fees_paid: 0, created_at: 1_700_000_000}
+      response = Nip47.parse_response(conn, response(%{result_type: "list_transactions", result: %{transactions: [tx]}}, request_id))
+      assert [%Nip47.Transaction{type: :incoming, amount: 1000, payment_hash: "11"}] = response.result
+    end
+
+    test "rejects responses not signed by the wallet", %{conn: conn} do
+      forged = Event.new(@client_pubkey, "x", 23195, []) |> Event.sign(@client_secret_key)
+      assert_raise ArgumentError, ~r/NIP-47 parse_response failed: Event is not from the connected wallet/, fn ->
+        Nip47.parse_response(conn, forged)
+      end
+    end
+  end
+
+  describe "parse_notification/2" do
+    test "parses a NIP-44 payment notification" do
+      payload = %{
+        notification_type: "payment_received",
+        notification: %{type: "incoming", invoice: "lnbc1...", preimage: "00", payment_hash: "11", amount: 1000, fees_paid: 0, created_at: 1_700_000_000, settled_at: 1_700_000_001}
+      }
+
+      event = wallet_event(23197, Nip44.encrypt(@wallet_secret_key, @client_pubkey, Jason.encode!(payload)), [["p", @client_pubkey]])
+      notification = Nip47.parse_notification(Nip47.parse_uri(@uri), event)
+      assert notification.type == :payment_received
+      assert notification.transaction.amount == 1000
+      assert notification.transaction.settled_at == 1_700_000_001
+    end
+  end
+
+  describe "wallet service" do
+    setup do
+      conn = Nip47.parse_uri(@uri)
+      %{conn: conn, request: Nip47.pay_invoice(conn, "lnbc1...", encryption: :nip44_v2, expiration: 4_000_000_000)}
+    end
+
+    test "info_event/3 builds an info event clients can parse" do
+      info = Nip47.info_event(@wallet_secret_key, [:pay_invoice, :get_balance], notifications: [:payment_received]) |> then(&Nip47.parse_info(@uri, &1))
+      assert info.wallet_pubkey == @wallet_pubkey
+      assert info.methods == [:pay_invoice, :get_balance]
+      assert info.notifications == [:payment_received]
+      assert Nip47.encryption(info) == :nip44_v2
+    end
+
+    test "parse_request/3 decrypts requests from authorized clients", %{request: request} do
+      parsed = Nip47.parse_request(@wallet_secret_key, request, [@client_pubkey])
+      assert parsed.id == Jason.decode!(request)["id"]
+      assert parsed.client_pubkey == @client_pubkey
+      assert parsed.method == :pay_invoice
+      assert parsed.params == %{"invoice" => "lnbc1..."}
+      assert parsed.encryption == :nip44_v2
+      assert parsed.expiration == 4_000_000_000
+    end
+
+    test "parse_request/3 rejects unauthorized clients and other wallets", %{request: request} do
+      assert_raise ArgumentError, ~r/NIP-47 parse_request failed: Connection is not authorized/, fn ->
+        Nip47.parse_request(@wallet_secret_key, request, [@wallet_pubkey])
+      end
+
+      assert_raise ArgumentError, ~r/NIP-47 parse_request failed: Request is not addressed to this wallet/, fn ->
+        Nip47.parse_request(@client_secret_key, request, [@client_pubkey])
+      end
+    end
+
+    test "respond/3 and respond_error/4 answer with the request's encryption", %{conn: conn, request: request} do
+      request_id = Jason.decode!(request)["id"]
+
+      response = Nip47.respond(@wallet_secret_key, request, %{preimage: "00"}) |> then(&Nip47.parse_response(conn, &1))
+      assert response.request_id == request_id
+      assert response.result_type == :pay_invoice
+      assert response.result == %{preimage: "00"}
+
+      response = Nip47.respond_error(@wallet_secret_key, request, :insufficient_balance, "no funds") |> then(&Nip47.parse_response(conn, &1))
+      assert response.error == %{code: :insufficient_balance, message: "no funds"}
+      assert response.result == nil
+    end
+
+    test "notification_event/5 builds a NIP-44 notification", %{conn: conn} do
+      tx = %Nip47.Transaction{
+        type: :incoming,
+        invoice: "lnbc1...",
+        preimage: "00",
+        payment_hash: "11",
+        amount: 1000,
+        fees_paid: 0,
+        created_at: 1_700_000_000,
+        settled_at: 1_700_000_001
+      }
+
+      event = Nip47.notification_event(@wallet_secret_key, @client_pubkey, :payment_received, tx, encryption: :nip44_v2)
+      assert Jason.decode!(event)["kind"] == 23197
+
+      notification = Nip47.parse_notification(conn, event)
+      assert notification.type == :payment_received
+      assert notification.transaction.amount == 1000
+    end
+
+    test "unknown methods can be answered with not_implemented", %{conn: conn} do
+      request = client_request(%{method: "get_budget", params: %{}}, &Nip44.encrypt/3, [["encryption", "nip44_v2"]])
+
+      parsed = Nip47.parse_request(@wallet_secret_key, request, [@client_pubkey])
+      assert parsed.method == "get_budget"
+      assert parsed.encryption == :nip44_v2
+
+      assert_raise ArgumentError, ~r/NIP-47 response failed:/, fn ->
+        Nip47.respond(@wallet_secret_key, request, %{budget: 1})
+      end
+
+      response = Nip47.respond_error(@wallet_secret_key, request, :not_implemented, "no budgets") |> then(&Nip47.parse_response(conn, &1))
+      assert response.result_type == "get_budget"
+      assert response.error == %{code: :not_implemented, message: "no budgets"}
+    end
+
+    test "requests with an unsupported encryption get an unsupported_encryption error", %{conn: conn} do
+      body = %{method: "get_balance", params: %{}}
+
+      for request <- [client_request(body, &Nip04.encrypt/3, [["encryption", "nip44_v2"]]), client_request(body, &Nip04.encrypt/3, [["encryption", "nip99"]])] do
+        assert_raise ArgumentError, ~r/NIP-47 parse_request failed: Unsupported encryption/, fn ->
+          Nip47.parse_request(@wallet_secret_key, request, [@client_pubkey])
+        end
+
+        assert_raise ArgumentError, ~r/NIP-47 response failed: Unsupported encryption/, fn ->
+          Nip47.respond(@wallet_secret_key, request, %{balance: 1})
+        end
+
+        reply = Nip47.respond_error(@wallet_secret_key, request, :unsupported_encryption, "use nip04")
+        response = Nip47.parse_response(conn, reply)
+        assert response.error == %{code: :unsupported_encryption, message: "use nip04"}
+        assert response.result_type == nil
+      end
+    end
+  end
+end
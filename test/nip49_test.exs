@@ -0,0 +1,61 @@
+defmodule NostrElixir.Nip49Test do
+  use ExUnit.Case, async: true
+  alias NostrElixir.Nip49
+
+  @ncryptsec "ncryptsec1qgg9947rlpvqu76pj5ecreduf9jxhselq2nae2kghhvd5g7dgjtcxfqtd67p9m0w57lspw8gsq6yphnm8623nsl8xn9j4jdzz84zm3frztj3z7s35vpzmqf6ksu8r89qk5z2zxfmu5gv8th8wclt0h4p"
+  # The test vector with log_n set to 30 (1 TiB of scrypt memory).
+  @expensive_ncryptsec "ncryptsec1qg09947rlpvqu76pj5ecreduf9jxhselq2nae2kghhvd5g7dgjtcxfqtd67p9m0w57lspw8gsq6yphnm8623nsl8xn9j4jdzz84zm3frztj3z7s35vpzmqf6ksu8r89qk5z2zxfmu5gv8th8wc9kscn0"
+  # The test vector with log_n set to 21, just above the maximum.
+  @over_limit_ncryptsec "ncryptsec1qg24947rlpvqu76pj5ecreduf9jxhselq2nae2kghhvd5g7dgjtcxfqtd67p9m0w57lspw8gsq6yphnm8623nsl8xn9j4jdzz84zm3frztj3z7s35vpzmqf6ksu8r89qk5z2zxfmu5gv8th8wcjahwx6"
+
+  test "decrypts the NIP-49 test vector" do
+    result = Nip49.decrypt(@ncryptsec, "nostr")
+    assert result.secret_key == "3501454135014541350145413501453fefb02227e449e57cf4d3a3ce05378683"
+    assert result.log_n == 16
+    assert result.key_security == :weak
+  end
+
+  test "round trip with custom log_n and key security" do
+    keys = NostrElixir.Keys.generate_keypair()
+    ncryptsec = Nip49.encrypt(keys, "password", log_n: 8, key_security: :medium)
+    assert String.starts_with?(ncryptsec, "ncryptsec1")
+
+    result = Nip49.decrypt(ncryptsec, "password")
+    assert result.secret_key == keys.secret_key
+    assert result.public_key == keys.public_key
+    assert result.log_n == 8
+    assert result.key_security == :medium
+  end
+
+  test "accepts a raw secret key" do
+    keys = NostrElixir.Keys.generate_keypair()
+    ncryptsec = Nip49.encrypt(keys.secret_key, "password", log_n: 8)
+    assert Nip49.decrypt(ncryptsec, "password").key_security == :unknown
+  end
+
+  test "raises on wrong password" do
+    assert_raise ArgumentError, ~r/NIP-49 decrypt failed:/, fn ->
+      Nip49.decrypt(@ncryptsec, "wrong")
+    end
+  end
+
+  test "raises on invalid secret key" do
+    assert_raise ArgumentError, ~r/NIP-49 encrypt failed:/, fn ->
+      Nip49.encrypt("invalid", "password", log_n: 8)
+    end
+  end
+
+  test "rejects a log_n above the maximum" do
+    assert_raise ArgumentError, ~r/NIP-49 decrypt failed: log_n 30 exceeds the maximum of 20/, fn ->
+      Nip49.decrypt(@expensive_ncryptsec, "nostr")
+    end
+
+    assert_raise ArgumentError, ~r/NIP-49 decrypt failed: log_n 21 exceeds the maximum of 20/, fn ->
+      Nip49.decrypt(@over_limit_ncryptsec, "nostr")
+    end
+
+    assert_raise ArgumentError, ~r/NIP-49 encrypt failed: log_n 21 exceeds the maximum of 20/, fn ->
+      Nip49.encrypt(NostrElixir.Keys.generate_keypair(), "password", log_n: 21)
+    end
+  end
+end
@@ -5,6 +5,16 @@ defmodule NostrElixir.Nip57Test do
 
   @alice_secret_key "5c0c523f52a5b6fad39ed2403092df8cebc36318b39383bca6c00808626fab3a"
   @bob_secret_key "4b22aa260e4acb7021e32f38a6cdf4b673c6a277755bfce287e370c924dc936d"
+  @bob_public_key "fa3b4f81a620c66514bda0302847df167ed02a483141b5939e57bdd0cf76ad3b"
+
+  @carol "b889ff5b1513b641e2a139f661a661364979c5beee91842f8f0ef42ab558e9d4"
+  @dave "87d3561f19b74adbe8bf840682992466068830a9d8c36b4a0c99d36f826cb6cb"
+
+  # Receipt signed by bob (the LNURL server) for a 21 sat zap request from alice.
+  @zap_receipt ~S({"id":"6638db01d833d958ca3b6ec63eb422140763efc3f748e308b5a61214ed53a8c5","pubkey":"fa3b4f81a620c66514bda0302847df167ed02a483141b5939e57bdd0cf76ad3b","created_at":1700000060,"kind":9735,"tags":[["p","b889ff5b1513b641e2a139f661a661364979c5beee91842f8f0ef42ab558e9d4"],["bolt11","lnbc210n1pj48ugqpp5qurswpc8qurswpc8qurswpc8qurswpc8qurswpc8qurswpc8qurshp5fkzyxc963x2qmjacezr0gxd50yss8z3tp7vvu8v6x2y2atlkuz2suc4kdyc20qcnyl62pld09xlfgthuqm8m3z0r5782ewkmkq8dz0skul2vexpckwdpkp80l2rmcec5yad04750hcs8vqy2kvmzfcrl9gqq0lzzrx"],["description","{\"id\":\"509048621fb9071fe95328d6b0a66322f83c005a925d688dc219a6363a913095\",\"pubkey\":\"87d3561f19b74adbe8bf840682992466068830a9d8c36b4a0c99d36f826cb6cb\",\"created_at\":1700000000,\"kind\":9734,\"tags\":[[\"relays\",\"wss://relay.damus.io\"],[\"amount\",\"21000\"],[\"p\",\"b889ff5b1513b641e2a139f661a661364979c5beee91842f8f0ef42ab558e9d4\"]],\"content\":\"Thanks!\",\"sig\":\"6890dc89f7cc25ed4ba9e1f687130be0d15dcb2d84918eedff648315a75c15e892d21e090e0fe1ccdb2f06cc85b7843c95ebb9e0bf3142cf2400c0cf84cdf846\"}"],["preimage","1111111111111111111111111111111111111111111111111111111111111111"]],"content":"","sig":"417a9e93c5cb4ddc849bc54d212563274d428eb3f51bafce66377b26588d73f183878e64bc033ab36c66f5498e6ac84e5068f5e39adab6239edb2626f7d8fb9d"})
+
+  # Same zap, but the invoice carries an all-zero signature no node could have produced.
+  @forged_invoice_receipt ~S({"id":"45fd84d5b2efe51c7ed31f2c09c06f8dc7841bb4f0b7792800c966c0ee3c92a2","pubkey":"fa3b4f81a620c66514bda0302847df167ed02a483141b5939e57bdd0cf76ad3b","created_at":1700000060,"kind":9735,"tags":[["p","b889ff5b1513b641e2a139f661a661364979c5beee91842f8f0ef42ab558e9d4"],["bolt11","lnbc210n1pj48ugqpp5qurswpc8qurswpc8qurswpc8qurswpc8qurswpc8qurswpc8qurshp5dcukax5n27cmwtz00r6hl5gq8c763x8jej7eralpg6l0etzvvvlsqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqxxua02"],["description","{\"id\":\"509048621fb9071fe95328d6b0a66322f83c005a925d688dc219a6363a913095\",\"pubkey\":\"87d3561f19b74adbe8bf840682992466068830a9d8c36b4a0c99d36f826cb6cb\",\"created_at\":1700000000,\"kind\":9734,\"tags\":[[\"relays\",\"wss://relay.damus.io\"],[\"amount\",\"21000\"],[\"p\",\"b889ff5b1513b641e2a139f661a661364979c5beee91842f8f0ef42ab558e9d4\"]],\"content\":\"Thanks!\",\"sig\":\"efd2abecbf549690bc3096b4d50964154928335fee1054079be794bf9e8fde478ffce80901b9bf3bc8cb42018bad8fd1187a715d009d245e60cf9bdc671052bb\"}"],["preimage","1111111111111111111111111111111111111111111111111111111111111111"]],"content":"","sig":"09d2c4a6d90320c39d30e7701d432dfe2f2c76b9cc2ad17ed6ebcb0fb90e464806d79eef2aabea4d9147acb15a871467dca048e447e3dbb2da0e3dc2a56cea1a"})
 
   describe "ZapRequestData.new/1" do
     test "builds struct with all fields" do
@@ -47,4 +57,182 @@ defmodule NostrElixir.Nip57Test do
       end
     end
   end
+
+  describe "validate_zap_receipt/2" do
+    test "returns the zap details for a valid receipt" do
+      receipt = Nip57.validate_zap_receipt(@zap_receipt, @bob_public_key)
+      assert %Nip57.ZapReceipt{} = receipt
+      assert receipt.sender == "87d3561f19b74adbe8bf840682992466068830a9d8c36b4a0c99d36f826cb6cb"
+      assert receipt.recipient == "b889ff5b1513b641e2a139f661a661364979c5beee91842f8f0ef42ab558e9d4"
+      assert receipt.amount == 21000
+      assert receipt.comment == "Thanks!"
+      assert receipt.event_id == nil
+      assert receipt.anonymous == false
+      assert receipt.type == :public
+      assert receipt.paid_at == 1_700_000_060
+    end
+
+    test "rejects a receipt whose invoice signature is invalid" do
+      assert_raise ArgumentError, ~r/NIP-57 validate_zap_receipt failed: Invalid invoice signature/, fn ->
+        Nip57.validate_zap_receipt(@forged_invoice_receipt, @bob_public_key)
+      end
+    end
+
+    test "rejects a receipt not signed by the nostrPubkey" do
+      assert_raise ArgumentError, ~r/NIP-57 validate_zap_receipt failed: .*nostrPubkey/, fn ->
+        Nip57.validate_zap_receipt(@zap_receipt, "b889ff5b1513b641e2a139f661a661364979c5beee91842f8f0ef42ab558e9d4")
+      end
+    end
+
+    test "rejects events that are not zap receipts" do
+      request = Nip57.anonymous_zap_request(ZapRequestData.new(public_key: "b889ff5b1513b641e2a139f661a661364979c5beee91842f8f0ef42ab558e9d4", relays: ["wss://relay.damus.io"], message: "", amount: 1000))
+      assert_raise ArgumentError, ~r/kind 9735/, fn ->
+        Nip57.validate_zap_receipt(request, @bob_public_key)
+      end
+    end
+  end
+
+  describe "validate_zap_request/2" do
+    setup do
+      receipt = Jason.decode!(@zap_receipt)
+      [_, description] = Enum.find(receipt["tags"], &match?(["description" | _], &1))
+      [_, bolt11] = Enum.find(receipt["tags"], &match?(["bolt11" | _], &1))
+      %{request: description, bolt11: bolt11}
+    end
+
+    test "returns the zap request data", %{request: request} do
+      data = Nip57.validate_zap_request(request, amount: 21000)
+      assert %ZapRequestData{} = data
+      assert data.public_key == "b889ff5b1513b641e2a139f661a661364979c5beee91842f8f0ef42ab558e9d4"
+      assert data.relays == ["wss://relay.damus.io"]
+      assert data.amount == 21000
+      assert data.message == "Thanks!"
+    end
+
+    test "rejects a request with more than one P tag" do
+      tags = [["p", @carol], ["P", @bob_public_key], ["P", @carol], ["relays", "wss://relay.damus.io"], ["amount", "21000"]]
+
+      request =
+        NostrElixir.Event.new("87d3561f19b74adbe8bf840682992466068830a9d8c36b4a0c99d36f826cb6cb", "", 9734, tags)
+        |> NostrElixir.Event.sign(@alice_secret_key)
+
+      assert_raise ArgumentError, ~r/NIP-57 validate_zap_request failed: Zap request must have at most one P tag/, fn ->
+        Nip57.validate_zap_request(request)
+      end
+    end
+
+    test "rejects an amount that does not match the invoice", %{request: request} do
+      assert_raise ArgumentError, ~r/NIP-57 validate_zap_request failed: .*amount/, fn ->
+        Nip57.validate_zap_request(request, amount: 1000)
+      end
+    end
+
+    test "builds a receipt that validates", %{request: request, bolt11: bolt11} do
+      receipt_json = Nip57.zap_receipt(request, bolt11, @bob_secret_key, paid_at: 1_700_000_100, preimage: String.duplicate("22", 32))
+      receipt = Nip57.validate_zap_receipt(receipt_json, @bob_public_key)
+      assert receipt.amount == 21000
+      assert receipt.paid_at == 1_700_000_100
+      assert receipt.preimage == String.duplicate("22", 32)
+      assert receipt.sender == "87d3561f19b74adbe8bf840682992466068830a9d8c36b4a0c99d36f826cb6cb"
+    end
+
+    test "refuses a receipt for an invoice that does not commit to the request", %{bolt11: bolt11} do
+      other = Nip57.anonymous_zap_request(ZapRequestData.new(public_key: "b889ff5b1513b641e2a139f661a661364979c5beee91842f8f0ef42ab558e9d4", relays: ["wss://relay.damus.io"], message: "", amount: 21000))
+      assert_raise ArgumentError, ~r/NIP-57 zap_receipt failed: .*description hash/, fn ->
+        Nip57.zap_receipt(other, bolt11, @bob_secret_key)
+      end
+    end
+  end
+
+  describe "zap splits" do
+    test "zap_split_tags/1 and zap_splits/1 round trip" do
+      splits = [
+        %Nip57.ZapSplit{public_key: @carol, relay: "wss://relay.damus.io", weight: 1},
+        %Nip57.ZapSplit{public_key: @dave, relay: nil, weight: 2}
+      ]
+
+      assert Nip57.zap_split_tags(splits) == [
+               ["zap", @carol, "wss://relay.damus.io", "1"],
+               ["zap", @dave, "", "2"]
+             ]
+
+      assert Nip57.zap_splits(split_event(splits)) == splits
+    end
+
+    test "split_amount/2 hands the remainder to the largest fractions" do
+      event = split_event([%{public_key: @carol, weight: 1}, %{public_key: @dave, weight: 2}])
+      assert [%{public_key: @carol, amount: 333}, %{public_key: @dave, amount: 667}] = Nip57.split_amount(event, 1000)
+    end
+
+    test "split_amount/2 splits equally without weights and skips unweighted recipients otherwise" do
+      equal = split_event([%{public_key: @carol}, %{public_key: @dave}, %{public_key: @bob_public_key}])
+      assert Enum.map(Nip57.split_amount(equal, 1000), & &1.amount) == [334, 333, 333]
+
+      partial = split_event([%{public_key: @carol, weight: 3}, %{public_key: @dave}])
+      assert Enum.map(Nip57.split_amount(partial, 1000), & &1.amount) == [1000, 0]
+    end
+
+    test "split_zap_requests/3 builds one request per recipient" do
+      event = split_event([%{public_key: @carol, relay: "wss://carol.relay", weight: 1}, %{public_key: @dave, weight: 1}], 30023, [["d", "post"]])
+      event_id = Jason.decode!(event)["id"]
+
+      requests = Nip57.split_zap_requests(event, 21000, relays: ["wss://relay.damus.io"], message: "great post", secret_key: @alice_secret_key)
+      assert [%{public_key: @carol, amount: 10500}, %{public_key: @dave, amount: 10500}] = requests
+
+      request = Nip57.validate_zap_request(hd(requests).zap_request, amount: 10500)
+      assert request.public_key == @carol
+      assert request.event_id == event_id
+      assert request.event_coordinate == "30023:#{@bob_public_key}:post"
+      assert request.relays == ["wss://relay.damus.io", "wss://carol.relay"]
+      assert request.message == "great post"
+    end
+
+    test "split_zap_requests/3 requires zap tags" do
+      event = NostrElixir.Event.new(@bob_public_key, "solo", 1, []) |> NostrElixir.Event.sign(@bob_secret_key)
+      assert_raise ArgumentError, ~r/NIP-57 split_zap_requests failed: Event has no zap tags/, fn ->
+        Nip57.split_zap_requests(event, 1000, relays: ["wss://relay.damus.io"])
+      end
+    end
+  end
+
+  describe "public_zap_request/2 and parse_zap_request/1" do
+    setup do
+      data = ZapRequestData.new(public_key: @carol, relays: ["wss://relay.damus.io"], message: "gm", amount: 2100, event_id: String.duplicate("0", 64))
+      %{data: data}
+    end
+
+    test "public requests are signed by the sender and keep the message", %{data: data} do
+      parsed = Nip57.parse_zap_request(Nip57.public_zap_request(data, @bob_secret_key))
+      assert parsed.type == :public
+      assert parsed.sender == @bob_public_key
+      assert parsed.public_key == @carol
+      assert parsed.relays == ["wss://relay.damus.io"]
+      assert parsed.message == "gm"
+      assert parsed.amount == 2100
+      assert parsed.event_id == String.duplicate("0", 64)
+      assert parsed.lnurl == nil
+    end
+
+    test "tells private and anonymous requests apart", %{data: data} do
+      private = Nip57.parse_zap_request(Nip57.private_zap_request(data, @bob_secret_key))
+      assert private.type == :private
+      assert private.message == ""
+      refute private.sender == @bob_public_key
+
+      anonymous = Nip57.parse_zap_request(Nip57.anonymous_zap_request(data))
+      assert anonymous.type == :anonymous
+      assert anonymous.message == "gm"
+    end
+
+    test "rejects events that are not zap requests" do
+      assert_raise ArgumentError, ~r/NIP-57 parse_zap_request failed: .*kind 9734/, fn ->
+        Nip57.parse_zap_request(@zap_receipt)
+      end
+    end
+  end
+
+  defp split_event(splits, kind \\ 1, extra_tags \\ []) do
+    NostrElixir.Event.new(@bob_public_key, "co-authored", kind, Nip57.zap_split_tags(splits) ++ extra_tags)
+    |> NostrElixir.Event.sign(@bob_secret_key)
+  end
 end
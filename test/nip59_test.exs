@@ -0,0 +1,76 @@
+defmodule NostrElixir.Nip59Test do
+  use ExUnit.Case, async: true
+  alias NostrElixir.Nip59
+
+  setup do
+    alice = NostrElixir.Keys.generate_keypair()
+    bob = NostrElixir.Keys.generate_keypair()
+
+    rumor =
+      Jason.encode!(%{
+        pubkey: alice.public_key,
+        created_at: 1_700_000_000,
+        kind: 1,
+        tags: [],
+        content: "secret"
+      })
+
+    %{alice: alice, bob: bob, rumor: rumor}
+  end
+
+  describe "seal/4, gift_wrap/3 and unwrap/2" do
+    test "round trip", %{alice: alice, bob: bob, rumor: rumor} do
+      seal = Nip59.seal(rumor, alice, bob.public_key)
+      assert Jason.decode!(seal)["kind"] == 13
+      assert Jason.decode!(seal)["pubkey"] == alice.public_key
+
+      gift_wrap = Nip59.gift_wrap(seal, bob.public_key)
+      event = Jason.decode!(gift_wrap)
+      assert event["kind"] == 1059
+      assert event["pubkey"] != alice.public_key
+      assert ["p", bob.public_key] in event["tags"]
+
+      unwrapped = Nip59.unwrap(gift_wrap, bob)
+      assert unwrapped.sender == alice.public_key
+      assert unwrapped.rumor["content"] == "secret"
+      assert is_binary(unwrapped.rumor["id"])
+      refute Map.has_key?(unwrapped.rumor, "sig")
+    end
+
+    test "supports extra tags, expiration and custom tweak", %{alice: alice, bob: bob, rumor: rumor} do
+      now = System.os_time(:second)
+      seal = Nip59.seal(rumor, alice, bob.public_key, tweak: 60..60)
+      assert_in_delta Jason.decode!(seal)["created_at"], now - 60, 5
+
+      gift_wrap =
+        Nip59.gift_wrap(seal, bob.public_key, tags: [["k", "1"]], expiration: now + 3600, tweak: 0..10)
+
+      event = Jason.decode!(gift_wrap)
+      assert ["k", "1"] in event["tags"]
+      assert ["expiration", Integer.to_string(now + 3600)] in event["tags"]
+      assert event["created_at"] <= now + 1
+    end
+
+    test "raises when the rumor author is not the sender", %{bob: bob, rumor: rumor} do
+      assert_raise ArgumentError, ~r/NIP-59 seal failed:/, fn ->
+        Nip59.seal(rumor, bob, bob.public_key)
+      end
+    end
+
+    test "raises when the rumor id does not match its content", %{alice: alice, bob: bob, rumor: rumor} do
+      rumor = rumor |> Jason.decode!() |> Map.put("id", String.duplicate("0", 64)) |> Jason.encode!()
+
+      assert_raise ArgumentError, ~r/NIP-59 seal failed:/, fn ->
+        Nip59.seal(rumor, alice, bob.public_key)
+      end
+    end
+
+    test "raises when unwrapping with the wrong key", %{alice: alice, bob: bob, rumor: rumor} do
+      gift_wrap = rumor |> Nip59.seal(alice, bob.public_key) |> Nip59.gift_wrap(bob.public_key)
+
+      assert_raise ArgumentError, ~r/NIP-59 unwrap failed:/, fn ->
+        Nip59.unwrap(gift_wrap, alice)
+      end
+    end
+  end
+end